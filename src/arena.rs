@@ -0,0 +1,220 @@
+//! Arena hazards: wall blocks, roaming hazards and a closing border that
+//! appear on a schedule as an arena run goes on. Walls and closed rings are
+//! added to the run's walls, so they collide like any other wall.
+
+use crate::food::lcg_next;
+use crate::{Board, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArenaSchedule {
+    pub wall_every_ticks: u64,
+    pub roamer_every_ticks: u64,
+    pub max_roamers: usize,
+    /// Roamers step once every this many ticks.
+    pub roamer_step_ticks: u64,
+    pub shrink_every_ticks: u64,
+    /// The border stops closing once the open area is this narrow.
+    pub min_span: i32,
+}
+
+impl ArenaSchedule {
+    /// Most hazards a run can have picked up by `tick`: one per scheduled
+    /// wall, roamer and border step.
+    pub fn max_level(&self, tick: u64) -> u64 {
+        [
+            self.wall_every_ticks,
+            self.roamer_every_ticks,
+            self.shrink_every_ticks,
+        ]
+        .into_iter()
+        .map(|every| tick / every.max(1))
+        .fold(0, u64::saturating_add)
+    }
+}
+
+pub const ARENA_SCHEDULE: ArenaSchedule = ArenaSchedule {
+    wall_every_ticks: 40,
+    roamer_every_ticks: 150,
+    max_roamers: 3,
+    roamer_step_ticks: 2,
+    shrink_every_ticks: 250,
+    min_span: 6,
+};
+
+/// New hazards keep at least this many steps away from the head.
+pub const SAFE_DISTANCE: i32 = 4;
+
+/// A hazard that drifts diagonally and bounces off anything in its way. A
+/// closing border ring removes any roamer caught on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Roamer {
+    pub position: Point,
+    pub dx: i32,
+    pub dy: i32,
+}
+
+impl Roamer {
+    /// Moves one step, reversing direction on whichever axis is blocked.
+    /// Stays put if boxed in.
+    pub fn step(&mut self, blocked: impl Fn(Point) -> bool) {
+        for (dx, dy) in [
+            (self.dx, self.dy),
+            (-self.dx, self.dy),
+            (self.dx, -self.dy),
+            (-self.dx, -self.dy),
+        ] {
+            let next = Point {
+                x: self.position.x + dx,
+                y: self.position.y + dy,
+            };
+            if !blocked(next) {
+                self.position = next;
+                self.dx = dx;
+                self.dy = dy;
+                return;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arena {
+    pub schedule: ArenaSchedule,
+    pub roamers: Vec<Roamer>,
+    /// Rings the border has closed in from the board edge.
+    pub inset: i32,
+    /// Hazards added so far: walls, roamers and closed rings.
+    pub level: u32,
+    pub seed: u64,
+}
+
+impl Arena {
+    pub fn new(schedule: ArenaSchedule, seed: u64) -> Self {
+        Self {
+            schedule,
+            roamers: Vec::new(),
+            inset: 0,
+            level: 0,
+            seed,
+        }
+    }
+
+    pub fn has_roamer_at(&self, point: Point) -> bool {
+        self.roamers.iter().any(|roamer| roamer.position == point)
+    }
+
+    /// Cells of the next border ring, or `None` once the arena is at its
+    /// smallest.
+    pub fn next_ring(&self, board: Board) -> Option<Vec<Point>> {
+        let (left, top) = (self.inset, self.inset);
+        let (right, bottom) = (board.width - 1 - self.inset, board.height - 1 - self.inset);
+        let span = (right - left + 1).min(bottom - top + 1);
+        if span - 2 < self.schedule.min_span {
+            return None;
+        }
+        let mut ring = Vec::new();
+        for x in left..=right {
+            ring.push(Point { x, y: top });
+            ring.push(Point { x, y: bottom });
+        }
+        for y in top + 1..bottom {
+            ring.push(Point { x: left, y });
+            ring.push(Point { x: right, y });
+        }
+        Some(ring)
+    }
+
+    /// Ticks until the next scheduled wall or border change.
+    pub fn ticks_until_next(&self, tick: u64) -> u64 {
+        let schedule = self.schedule;
+        [schedule.wall_every_ticks, schedule.shrink_every_ticks]
+            .into_iter()
+            .map(|every| every.max(1))
+            .map(|every| every - tick % every)
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+/// Picks a free cell inside the border `inset` rings in, away from `head`.
+/// Returns the cell, if any, and the advanced seed.
+pub fn pick_cell(
+    seed: u64,
+    board: Board,
+    inset: i32,
+    head: Point,
+    blocked: impl Fn(Point) -> bool,
+) -> (Option<Point>, u64) {
+    let candidates: Vec<Point> = (inset..board.height - inset)
+        .flat_map(|y| (inset..board.width - inset).map(move |x| Point { x, y }))
+        .filter(|cell| {
+            (cell.x - head.x).abs() + (cell.y - head.y).abs() >= SAFE_DISTANCE && !blocked(*cell)
+        })
+        .collect();
+    let seed = lcg_next(seed);
+    if candidates.is_empty() {
+        return (None, seed);
+    }
+    (
+        Some(candidates[(seed >> 33) as usize % candidates.len()]),
+        seed,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rings_close_inward_until_the_minimum_span() {
+        let board = Board {
+            width: 12,
+            height: 12,
+        };
+        let mut arena = Arena::new(ARENA_SCHEDULE, 1);
+        let ring = arena.next_ring(board).unwrap();
+        assert_eq!(ring.len(), 44);
+        assert!(
+            ring.iter()
+                .all(|cell| cell.x == 0 || cell.y == 0 || cell.x == 11 || cell.y == 11)
+        );
+        arena.inset = 2;
+        assert_eq!(arena.next_ring(board).unwrap().len(), 28);
+        arena.inset = 3;
+        assert!(arena.next_ring(board).is_none());
+
+        let unpaced = Arena::new(
+            ArenaSchedule {
+                wall_every_ticks: 0,
+                ..ARENA_SCHEDULE
+            },
+            1,
+        );
+        assert_eq!(unpaced.ticks_until_next(7), 1);
+    }
+
+    #[test]
+    fn roamers_bounce_and_picked_cells_stay_clear_of_the_head() {
+        let mut roamer = Roamer {
+            position: Point { x: 3, y: 0 },
+            dx: 1,
+            dy: -1,
+        };
+        roamer.step(|cell| cell.y < 0);
+        assert_eq!(roamer.position, Point { x: 4, y: 1 });
+        assert_eq!((roamer.dx, roamer.dy), (1, 1));
+
+        let board = Board {
+            width: 6,
+            height: 6,
+        };
+        let head = Point { x: 0, y: 0 };
+        let mut seed = 7;
+        for _ in 0..20 {
+            let (cell, next) = pick_cell(seed, board, 0, head, |cell| cell.x == 5);
+            let cell = cell.unwrap();
+            assert!(cell.x + cell.y >= SAFE_DISTANCE && cell.x != 5);
+            seed = next;
+        }
+    }
+}
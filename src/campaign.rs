@@ -0,0 +1,128 @@
+//! The campaign: an ordered list of stages, each with its own board, food
+//! layout, pace and goal. Clearing a stage unlocks the next one.
+
+use crate::{Board, FoodRules, RunMetrics, SpeedCurve};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageGoal {
+    EatFood(u64),
+    SurviveTicks(u64),
+    ReachLength(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stage {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub board: Board,
+    pub food_rules: FoodRules,
+    pub start_speed_percent: u32,
+    pub speed_curve: SpeedCurve,
+    pub goal: StageGoal,
+}
+
+pub const STAGES: &[Stage] = &[
+    Stage {
+        id: "meadow",
+        name: "Meadow",
+        board: Board {
+            width: 12,
+            height: 12,
+        },
+        food_rules: FoodRules::single_apple(),
+        start_speed_percent: 90,
+        speed_curve: SpeedCurve::Flat,
+        goal: StageGoal::EatFood(5),
+    },
+    Stage {
+        id: "orchard",
+        name: "Orchard",
+        board: Board {
+            width: 14,
+            height: 10,
+        },
+        food_rules: FoodRules::multi_food(),
+        start_speed_percent: 100,
+        speed_curve: SpeedCurve::Flat,
+        goal: StageGoal::EatFood(12),
+    },
+    Stage {
+        id: "thicket",
+        name: "Thicket",
+        board: Board {
+            width: 10,
+            height: 10,
+        },
+        food_rules: FoodRules::cluster(),
+        start_speed_percent: 100,
+        speed_curve: SpeedCurve::ByLength {
+            every_growth: 4,
+            max_level: 6,
+        },
+        goal: StageGoal::ReachLength(15),
+    },
+    Stage {
+        id: "storm",
+        name: "Storm",
+        board: Board {
+            width: 12,
+            height: 12,
+        },
+        food_rules: FoodRules::timed_waves(),
+        start_speed_percent: 110,
+        speed_curve: SpeedCurve::ByTime {
+            every_ticks: 80,
+            max_level: 8,
+        },
+        goal: StageGoal::SurviveTicks(400),
+    },
+    Stage {
+        id: "summit",
+        name: "Summit",
+        board: Board {
+            width: 16,
+            height: 12,
+        },
+        food_rules: FoodRules::multi_food(),
+        start_speed_percent: 120,
+        speed_curve: SpeedCurve::ByTime {
+            every_ticks: 60,
+            max_level: 10,
+        },
+        goal: StageGoal::EatFood(25),
+    },
+];
+
+impl Stage {
+    pub fn goal_met(&self, metrics: &RunMetrics, snake_length: usize) -> bool {
+        match self.goal {
+            StageGoal::EatFood(count) => metrics.food_eaten >= count,
+            StageGoal::SurviveTicks(ticks) => metrics.survival_ticks >= ticks,
+            StageGoal::ReachLength(length) => snake_length >= length,
+        }
+    }
+}
+
+/// Index of the stage with `id`.
+pub fn stage_index(id: &str) -> Option<usize> {
+    STAGES.iter().position(|stage| stage.id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::Point;
+
+    #[test]
+    fn stages_have_unique_ids_and_fit_the_starting_snake() {
+        let mut ids = HashSet::new();
+        for stage in STAGES {
+            assert!(ids.insert(stage.id), "duplicate id {}", stage.id);
+            assert!(Point { x: 5, y: 5 }.within(stage.board), "{}", stage.id);
+            assert!(Point { x: 7, y: 5 }.within(stage.board), "{}", stage.id);
+        }
+        assert_eq!(stage_index("storm"), Some(3));
+    }
+}
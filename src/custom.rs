@@ -0,0 +1,115 @@
+//! Player-composed rules for custom mode. Presets are saved by name in the
+//! profile, and custom runs rank on their own leaderboard tagged with the
+//! preset they were played with.
+
+use std::collections::BTreeSet;
+
+use crate::{CollisionOutcome, FoodRules, SpeedCurve};
+
+/// Points awarded per food, per ten ticks survived and per speed bonus point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreWeights {
+    pub per_food: u32,
+    pub per_ten_ticks: u32,
+    pub per_speed_point: u32,
+}
+
+/// Highest value any single score weight can take.
+pub const MAX_WEIGHT: u32 = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomRules {
+    pub name: String,
+    pub collision: CollisionOutcome,
+    pub wraps: bool,
+    pub weights: ScoreWeights,
+    pub food_rules: FoodRules,
+    pub speed_curve: SpeedCurve,
+    /// Tools active for the whole run; each must be unlocked.
+    pub tools: BTreeSet<String>,
+}
+
+pub const FOOD_OPTIONS: [(&str, FoodRules); 4] = [
+    ("single apple", FoodRules::single_apple()),
+    ("multi food", FoodRules::multi_food()),
+    ("cluster", FoodRules::cluster()),
+    ("timed waves", FoodRules::timed_waves()),
+];
+
+pub const SPEED_OPTIONS: [(&str, SpeedCurve); 3] = [
+    ("flat", SpeedCurve::Flat),
+    (
+        "ramps over time",
+        SpeedCurve::ByTime {
+            every_ticks: 100,
+            max_level: 10,
+        },
+    ),
+    (
+        "ramps with length",
+        SpeedCurve::ByLength {
+            every_growth: 5,
+            max_level: 8,
+        },
+    ),
+];
+
+impl Default for CustomRules {
+    fn default() -> Self {
+        Self {
+            name: "Custom".to_string(),
+            collision: CollisionOutcome::Die,
+            wraps: false,
+            weights: ScoreWeights {
+                per_food: 10,
+                per_ten_ticks: 1,
+                per_speed_point: 1,
+            },
+            food_rules: FOOD_OPTIONS[0].1,
+            speed_curve: SPEED_OPTIONS[0].1,
+            tools: BTreeSet::new(),
+        }
+    }
+}
+
+impl CustomRules {
+    pub fn food_option(&self) -> usize {
+        FOOD_OPTIONS
+            .iter()
+            .position(|(_, rules)| *rules == self.food_rules)
+            .unwrap_or(0)
+    }
+
+    pub fn speed_option(&self) -> usize {
+        SPEED_OPTIONS
+            .iter()
+            .position(|(_, curve)| *curve == self.speed_curve)
+            .unwrap_or(0)
+    }
+
+    /// Active tools joined like a loadout summary, or "none".
+    pub fn tools_summary(&self) -> String {
+        if self.tools.is_empty() {
+            "none".to_string()
+        } else {
+            self.tools.iter().cloned().collect::<Vec<_>>().join("+")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_round_trip_and_tools_summarise() {
+        let mut rules = CustomRules::default();
+        assert_eq!(rules.tools_summary(), "none");
+        rules.food_rules = FOOD_OPTIONS[2].1;
+        rules.speed_curve = SPEED_OPTIONS[1].1;
+        assert_eq!((rules.food_option(), rules.speed_option()), (2, 1));
+        rules.tools.insert("soft-wrap".to_string());
+        rules.tools.insert("slow-window".to_string());
+        assert_eq!(rules.tools_summary(), "slow-window+soft-wrap");
+    }
+}
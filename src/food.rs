@@ -0,0 +1,373 @@
+//! Food kinds and the per-kind rules shared by every mode: how often each
+//! spawns, how long it stays on the board and how it changes the snake.
+//! [`FoodRules`] describes how a mode lays food out, and the placement helpers
+//! here pick free cells for it.
+
+use crate::{Board, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FoodKind {
+    Normal,
+    /// Grows the snake by several segments at once.
+    Golden,
+    /// Sheds tail segments instead of growing.
+    Shrinking,
+    /// Temporarily speeds up the simulation.
+    SpeedUp,
+    /// Worth extra points but disappears if not eaten in time.
+    Bonus,
+}
+
+/// Ticks a speed-up food keeps the faster pace.
+pub const SPEED_UP_TICKS: u32 = 25;
+/// Tail segments removed by shrinking food, never cutting below the minimum.
+pub const SHRINK_SEGMENTS: usize = 2;
+pub const MIN_SNAKE_LENGTH: usize = 2;
+
+impl FoodKind {
+    pub const ALL: [FoodKind; 5] = [
+        FoodKind::Normal,
+        FoodKind::Golden,
+        FoodKind::Shrinking,
+        FoodKind::SpeedUp,
+        FoodKind::Bonus,
+    ];
+
+    /// Relative spawn weight; kinds are drawn proportionally to these.
+    pub fn spawn_weight(self) -> u32 {
+        match self {
+            FoodKind::Normal => 70,
+            FoodKind::Golden => 8,
+            FoodKind::Shrinking => 8,
+            FoodKind::SpeedUp => 8,
+            FoodKind::Bonus => 6,
+        }
+    }
+
+    /// Ticks before the food vanishes, or `None` if it stays until eaten.
+    pub fn lifetime_ticks(self) -> Option<u64> {
+        match self {
+            FoodKind::Golden => Some(80),
+            FoodKind::Bonus => Some(40),
+            FoodKind::Normal | FoodKind::Shrinking | FoodKind::SpeedUp => None,
+        }
+    }
+
+    /// Growth units gained when eaten; shrinking food gains none.
+    pub fn growth(self) -> u64 {
+        match self {
+            FoodKind::Golden => 3,
+            FoodKind::Shrinking => 0,
+            FoodKind::Normal | FoodKind::SpeedUp | FoodKind::Bonus => 1,
+        }
+    }
+
+    /// Picks a kind from a uniform `roll` using the spawn weights.
+    pub fn from_roll(roll: u64) -> FoodKind {
+        let total: u32 = FoodKind::ALL.iter().map(|kind| kind.spawn_weight()).sum();
+        let mut remaining = (roll % total as u64) as u32;
+        for kind in FoodKind::ALL {
+            if remaining < kind.spawn_weight() {
+                return kind;
+            }
+            remaining -= kind.spawn_weight();
+        }
+        FoodKind::Normal
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Food {
+    pub kind: FoodKind,
+    pub position: Point,
+    /// Survival tick at which the food disappears.
+    pub expires_at_tick: Option<u64>,
+}
+
+impl Food {
+    pub fn new(kind: FoodKind, position: Point, spawned_at_tick: u64) -> Self {
+        Self {
+            kind,
+            position,
+            expires_at_tick: kind
+                .lifetime_ticks()
+                .map(|lifetime| spawned_at_tick.saturating_add(lifetime)),
+        }
+    }
+
+    pub fn is_expired(&self, tick: u64) -> bool {
+        self.expires_at_tick.is_some_and(|expires| tick >= expires)
+    }
+
+    /// Fraction of lifetime left, `1.0` for permanent food.
+    pub fn remaining_fraction(&self, tick: u64) -> f32 {
+        match (self.expires_at_tick, self.kind.lifetime_ticks()) {
+            (Some(expires), Some(lifetime)) => {
+                expires.saturating_sub(tick) as f32 / lifetime.max(1) as f32
+            }
+            _ => 1.0,
+        }
+    }
+}
+
+/// How a mode distributes food over the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoodPattern {
+    /// One apple at a time, replaced as soon as it is eaten.
+    SingleApple,
+    /// Several separated foods, topped up in batches.
+    MultiFood,
+    /// Batches spawn as touching groups.
+    Cluster,
+    /// Batches arrive on a tick timer instead of when food is eaten.
+    TimedWaves,
+    /// Only food placed by a layout; nothing ever spawns.
+    Fixed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FoodRules {
+    pub pattern: FoodPattern,
+    pub initial_count: usize,
+    /// Refill after every this many foods eaten; `0` disables eat refills.
+    pub refill_every_eaten: u64,
+    pub refill_count: usize,
+    /// Spawn a batch every this many ticks; `0` disables waves.
+    pub wave_every_ticks: u64,
+    pub max_on_board: usize,
+    /// Forbid new food touching existing food, diagonals included.
+    pub keep_apart: bool,
+    /// Allow kinds other than [`FoodKind::Normal`].
+    pub special_kinds: bool,
+}
+
+impl FoodRules {
+    pub const fn single_apple() -> Self {
+        Self {
+            pattern: FoodPattern::SingleApple,
+            initial_count: 1,
+            refill_every_eaten: 1,
+            refill_count: 1,
+            wave_every_ticks: 0,
+            max_on_board: 1,
+            keep_apart: false,
+            special_kinds: false,
+        }
+    }
+
+    pub const fn multi_food() -> Self {
+        Self {
+            pattern: FoodPattern::MultiFood,
+            initial_count: 6,
+            refill_every_eaten: 2,
+            refill_count: 3,
+            wave_every_ticks: 0,
+            max_on_board: 24,
+            keep_apart: true,
+            special_kinds: true,
+        }
+    }
+
+    pub const fn cluster() -> Self {
+        Self {
+            pattern: FoodPattern::Cluster,
+            initial_count: 4,
+            refill_every_eaten: 4,
+            refill_count: 4,
+            wave_every_ticks: 0,
+            max_on_board: 16,
+            keep_apart: false,
+            special_kinds: true,
+        }
+    }
+
+    pub const fn timed_waves() -> Self {
+        Self {
+            pattern: FoodPattern::TimedWaves,
+            initial_count: 3,
+            refill_every_eaten: 0,
+            refill_count: 4,
+            wave_every_ticks: 30,
+            max_on_board: 12,
+            keep_apart: true,
+            special_kinds: true,
+        }
+    }
+
+    pub const fn fixed() -> Self {
+        Self {
+            pattern: FoodPattern::Fixed,
+            initial_count: 0,
+            refill_every_eaten: 0,
+            refill_count: 0,
+            wave_every_ticks: 0,
+            max_on_board: 0,
+            keep_apart: false,
+            special_kinds: false,
+        }
+    }
+
+    /// Scales food counts by `percent`, keeping at least one of each. The
+    /// single-apple and fixed patterns define the ruleset by their counts, so
+    /// they are left as they are.
+    pub fn scaled(self, percent: u32) -> Self {
+        if matches!(self.pattern, FoodPattern::SingleApple | FoodPattern::Fixed) {
+            return self;
+        }
+        let scale = |count: usize| (count * percent as usize).div_ceil(100).max(1);
+        Self {
+            initial_count: scale(self.initial_count),
+            refill_count: scale(self.refill_count),
+            max_on_board: scale(self.max_on_board),
+            ..self
+        }
+    }
+
+    /// Foods to add after the `food_eaten`-th food, before the board cap.
+    pub fn refill_after_eating(&self, food_eaten: u64) -> usize {
+        if self.refill_every_eaten > 0 && food_eaten.is_multiple_of(self.refill_every_eaten) {
+            self.refill_count
+        } else {
+            0
+        }
+    }
+
+    /// Foods to add when `tick` starts a new wave.
+    pub fn wave_at(&self, tick: u64) -> usize {
+        if self.wave_every_ticks > 0 && tick > 0 && tick.is_multiple_of(self.wave_every_ticks) {
+            self.refill_count
+        } else {
+            0
+        }
+    }
+}
+
+/// Where a new food can go; a full board is reported rather than guessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoodPlacement {
+    Cell(Point),
+    BoardFull,
+}
+
+pub fn points_touch_or_adjacent(a: Point, b: Point) -> bool {
+    (a.x - b.x).abs() <= 1 && (a.y - b.y).abs() <= 1
+}
+
+pub(crate) fn lcg_next(seed: u64) -> u64 {
+    seed.wrapping_mul(6364136223846793005).wrapping_add(1)
+}
+
+/// Picks a free cell at random, honouring `keep_apart` while random probes
+/// last, then scanning for any free cell before giving up.
+pub fn next_food_position(
+    seed: u64,
+    board: Board,
+    snake: &[Point],
+    occupied_foods: &[Point],
+    keep_apart: bool,
+) -> (FoodPlacement, u64) {
+    let width = board.width.max(1) as usize;
+    let height = board.height.max(1) as usize;
+    let total = width.saturating_mul(height).max(1);
+    let cell = |idx: usize| Point {
+        x: (idx % width) as i32,
+        y: (idx / width) as i32,
+    };
+    let is_free =
+        |candidate: Point| !snake.contains(&candidate) && !occupied_foods.contains(&candidate);
+    let mut rng = seed;
+
+    for _ in 0..total.saturating_mul(2) {
+        rng = lcg_next(rng);
+        let candidate = cell((rng as usize) % total);
+        if is_free(candidate)
+            && !(keep_apart
+                && occupied_foods
+                    .iter()
+                    .any(|food| points_touch_or_adjacent(*food, candidate)))
+        {
+            return (FoodPlacement::Cell(candidate), rng);
+        }
+    }
+
+    match (0..total).map(cell).find(|candidate| is_free(*candidate)) {
+        Some(candidate) => (FoodPlacement::Cell(candidate), lcg_next(rng)),
+        None => (FoodPlacement::BoardFull, lcg_next(rng)),
+    }
+}
+
+/// First free orthogonal neighbour of any cell in `group`, used to grow clusters.
+pub fn free_neighbour(
+    board: Board,
+    snake: &[Point],
+    occupied: &[Point],
+    group: &[Point],
+) -> Option<Point> {
+    group.iter().find_map(|anchor| {
+        [(1, 0), (0, 1), (-1, 0), (0, -1)]
+            .into_iter()
+            .map(|(dx, dy)| Point {
+                x: anchor.x + dx,
+                y: anchor.y + dy,
+            })
+            .find(|candidate| {
+                candidate.within(board)
+                    && !snake.contains(candidate)
+                    && !occupied.contains(candidate)
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolls_follow_spawn_weights() {
+        let mut counts = [0u32; 5];
+        for roll in 0..100 {
+            let kind = FoodKind::from_roll(roll);
+            counts[FoodKind::ALL.iter().position(|k| *k == kind).unwrap()] += 1;
+        }
+        assert_eq!(counts, [70, 8, 8, 8, 6]);
+    }
+
+    #[test]
+    fn placement_reports_a_full_board() {
+        let board = Board {
+            width: 2,
+            height: 1,
+        };
+        let snake = [Point { x: 0, y: 0 }];
+        let (placement, _) = next_food_position(7, board, &snake, &[], true);
+        assert_eq!(placement, FoodPlacement::Cell(Point { x: 1, y: 0 }));
+
+        let (placement, _) = next_food_position(7, board, &snake, &[Point { x: 1, y: 0 }], true);
+        assert_eq!(placement, FoodPlacement::BoardFull);
+    }
+
+    #[test]
+    fn refills_and_waves_follow_the_pattern() {
+        let multi = FoodRules::multi_food();
+        assert_eq!(multi.refill_after_eating(1), 0);
+        assert_eq!(multi.refill_after_eating(2), 3);
+        assert_eq!(multi.wave_at(30), 0);
+
+        let waves = FoodRules::timed_waves();
+        assert_eq!(waves.refill_after_eating(2), 0);
+        assert_eq!(waves.wave_at(0), 0);
+        assert_eq!(waves.wave_at(60), 4);
+    }
+
+    #[test]
+    fn timed_food_expires_after_its_lifetime() {
+        let at = Point { x: 1, y: 1 };
+        let bonus = Food::new(FoodKind::Bonus, at, 10);
+        assert!(!bonus.is_expired(49));
+        assert!(bonus.is_expired(50));
+        assert_eq!(bonus.remaining_fraction(30), 0.5);
+
+        let normal = Food::new(FoodKind::Normal, at, 10);
+        assert!(!normal.is_expired(u64::MAX));
+    }
+}
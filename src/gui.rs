@@ -1,43 +1,94 @@
 use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use macroquad::prelude::*;
 
-use crate::{GameEngine, GameMode, GameRun, Point, Profile, ToolCategory, policy_for};
+use self::audio::{MacroquadAudio, SoundEvent, SoundManager};
+use self::effects::Effects;
+use self::layout::{BoardLayout, DESIGN_HEIGHT, DESIGN_WIDTH, Layout};
+use self::theme::{Theme, theme_label};
+use crate::food::lcg_next;
+use crate::{
+    CollisionOutcome, CustomRules, Difficulty, EffectsIntensity, ExportFormat, FOOD_OPTIONS, Food,
+    FoodKind, GameEngine, GameMode, GameRun, LeaderboardEntry, LeaderboardFilter,
+    MAX_PROFILE_NAME_LEN, MAX_PROFILES, MAX_WEIGHT, PUZZLES, Point, PointerControl, PointerTuning,
+    Profile, PuzzleGoal, RankField, RankKey, RankOrder, Ranking, RunEvent, SPEED_OPTIONS, STAGES,
+    SnakeRenderStyle, SpeedCurve, StageGoal, ThemeId, ToolCategory, unix_now,
+};
+
+mod audio;
+mod effects;
+mod layout;
+mod synth;
+mod theme;
 
-const WINDOW_WIDTH: i32 = 1000;
-const WINDOW_HEIGHT: i32 = 760;
 const SIM_TICK_SECONDS: f32 = 0.18;
+const REPOSITION_TRAIL_SECONDS: f32 = 0.6;
+const GRACE_BLINK_HZ: f32 = 6.0;
 const REPLAY_SECONDS: f32 = 0.85;
-const CELL_SIZE: f32 = 32.0;
-const INITIAL_FOOD_COUNT: usize = 6;
-const FOOD_REFILL_EVERY_EATEN: u64 = 2;
-const FOOD_REFILL_COUNT: usize = 3;
-const POINTER_IDLE_SECONDS_OUTSIDE_BOARD: f32 = 0.01;
-const POINTER_DISPLACEMENT_THRESHOLD: f32 = 2.0;
-const POINTER_DWELL_SECONDS: f32 = 0.45;
-const POINTER_IDLE_GRACE_SECONDS: f32 = 0.2;
-
-const MAIN_MENU_ITEMS: [&str; 3] = ["Play", "Leaderboards", "Settings"];
-const MODES: [GameMode; 4] = [
-    GameMode::Practice,
-    GameMode::Challenge,
-    GameMode::Experimental,
-    GameMode::Invincible,
+
+const MAIN_MENU_ITEMS: [&str; 4] = ["Play", "Leaderboards", "Settings", "Profiles"];
+const PAUSE_MENU_ITEMS: [&str; 4] = ["Resume", "Restart Same Seed", "Settings", "Abandon"];
+const SETTINGS_ROW_COUNT: usize = 16;
+/// Custom builder rows before the per-tool toggles.
+const CUSTOM_FIXED_ROWS: usize = 8;
+const SETTINGS_VISIBLE_ROWS: usize = 12;
+/// Leaderboard controls picked with Up/Down and changed with Left/Right.
+const LEADERBOARD_CONTROLS: [&str; 8] = [
+    "Mode",
+    "Difficulty",
+    "Loadout",
+    "Board",
+    "Date",
+    "View",
+    "Files",
+    "Page",
+];
+/// File actions on the leaderboard, run with Enter: label, format and
+/// whether the action imports.
+const LEADERBOARD_FILE_ACTIONS: [(&str, ExportFormat, bool); 4] = [
+    ("export CSV", ExportFormat::Csv, false),
+    ("export JSON", ExportFormat::Json, false),
+    ("import CSV", ExportFormat::Csv, true),
+    ("import JSON", ExportFormat::Json, true),
+];
+const LEADERBOARD_PAGE_ROWS: usize = 10;
+/// Date filters, as how far back from now they reach.
+const LEADERBOARD_DATE_RANGES: [(&str, Option<u64>); 4] = [
+    ("all time", None),
+    ("last 24h", Some(86_400)),
+    ("last 7 days", Some(7 * 86_400)),
+    ("last 30 days", Some(30 * 86_400)),
+];
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
 ];
+/// Time-attack clock shown as red below this many seconds.
+const TIME_WARNING_SECONDS: f32 = 10.0;
 
 pub fn window_conf() -> Conf {
     Conf {
         window_title: "Snake GUI Demo".to_string(),
-        window_width: WINDOW_WIDTH,
-        window_height: WINDOW_HEIGHT,
+        window_width: DESIGN_WIDTH as i32,
+        window_height: DESIGN_HEIGHT as i32,
         window_resizable: true,
         ..Default::default()
     }
 }
 
 pub async fn run_app() {
-    let mut app = SnakeGuiApp::new();
+    run_app_with(SnakeGuiApp::new()).await;
+}
+
+/// Runs `app`, e.g. one built with extra modes via [`SnakeGuiApp::with_engine`].
+pub async fn run_app_with(mut app: SnakeGuiApp) {
+    app.audio = SoundManager::new(Box::new(MacroquadAudio::load(app.modes()).await));
+    app.open_profile_select();
     loop {
         let dt = get_frame_time();
         app.update(dt);
@@ -48,8 +99,13 @@ pub async fn run_app() {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ScreenState {
+    ProfileSelect,
+    NameEntry,
     MainMenu,
     ModeSelect,
+    PuzzleSelect,
+    CampaignMap,
+    CustomBuilder,
     Loadout,
     Running,
     Summary,
@@ -71,15 +127,21 @@ enum UiCommand {
 enum RunningPhase {
     Active,
     PointerIdlePause,
+    Paused,
     Replay,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PointerFocusTarget {
+    ProfileItem(usize),
     MainMenuItem(usize),
     ModeItem(usize),
+    PuzzleItem(usize),
+    CampaignStage(usize),
+    CustomRow(usize),
     LoadoutSlot(usize),
-    SettingsToggle,
+    SettingsRow(usize),
+    PauseItem(usize),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -91,6 +153,27 @@ enum Direction {
 }
 
 impl Direction {
+    /// Heading implied by the first two segments of `snake`.
+    fn facing(snake: &[Point]) -> Option<Direction> {
+        let (head, neck) = (snake.first()?, snake.get(1)?);
+        match (head.x - neck.x, head.y - neck.y) {
+            (0, -1) => Some(Direction::Up),
+            (0, 1) => Some(Direction::Down),
+            (-1, 0) => Some(Direction::Left),
+            (1, 0) => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    fn command(self) -> UiCommand {
+        match self {
+            Direction::Up => UiCommand::Up,
+            Direction::Down => UiCommand::Down,
+            Direction::Left => UiCommand::Left,
+            Direction::Right => UiCommand::Right,
+        }
+    }
+
     fn opposite(self, other: Direction) -> bool {
         matches!(
             (self, other),
@@ -126,6 +209,63 @@ impl Direction {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PointerGesture {
+    Swipe(Direction),
+    Tap,
+}
+
+/// Turns press/drag/release samples into discrete swipes and taps. Touch
+/// input reaches this through macroquad's mouse emulation.
+#[derive(Debug, Clone, Default)]
+struct SwipeTracker {
+    origin: Option<Vec2>,
+    elapsed: f32,
+    swiped: bool,
+}
+
+impl SwipeTracker {
+    fn update(
+        &mut self,
+        dt: f32,
+        pointer_position: Vec2,
+        primary_down: bool,
+        primary_pressed: bool,
+        tuning: PointerTuning,
+    ) -> Option<PointerGesture> {
+        if primary_pressed || (primary_down && self.origin.is_none()) {
+            self.origin = Some(pointer_position);
+            self.elapsed = 0.0;
+            self.swiped = false;
+            return None;
+        }
+
+        let origin = self.origin?;
+        self.elapsed += dt;
+        let travel = pointer_position - origin;
+        let distance = travel.length();
+        if primary_down {
+            let speed = distance / self.elapsed.max(f32::EPSILON);
+            if distance >= tuning.swipe_min_px as f32
+                && speed >= tuning.swipe_min_px_per_second as f32
+            {
+                // Re-anchor so one continuous drag can chain several turns.
+                self.origin = Some(pointer_position);
+                self.elapsed = 0.0;
+                self.swiped = true;
+                return direction_from_delta(travel, 0.0).map(PointerGesture::Swipe);
+            }
+            return None;
+        }
+
+        self.origin = None;
+        let is_tap = !self.swiped
+            && distance < tuning.swipe_min_px as f32
+            && self.elapsed <= tuning.tap_max_seconds();
+        is_tap.then_some(PointerGesture::Tap)
+    }
+}
+
 #[derive(Debug, Clone)]
 struct LoadoutState {
     slot_cursor: usize,
@@ -144,40 +284,70 @@ impl Default for LoadoutState {
 #[derive(Debug, Clone)]
 struct RunningState {
     run: GameRun,
+    previous_snake: Vec<Point>,
     direction: Direction,
     queued_directions: VecDeque<Direction>,
     phase: RunningPhase,
     replay_timer: f32,
     accumulator: f32,
     tick_seconds: f32,
-    foods: Vec<Point>,
     spawn_seed: u64,
+    initial_seed: u64,
+    pause_cursor: usize,
     replay_path: Vec<Point>,
     pointer_idle_anchor: Option<Vec2>,
     pointer_idle_elapsed: f32,
     idle_grace_timer: f32,
+    blink_clock: f32,
+    reposition_trail: Option<RepositionTrail>,
+}
+
+/// Fading line from where the head collided to where Invincible placed it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RepositionTrail {
+    from: Point,
+    to: Point,
+    remaining: f32,
 }
 
 impl RunningState {
     fn new(run: GameRun) -> Self {
         Self {
+            previous_snake: run.snake.clone(),
+            direction: Direction::facing(&run.snake).unwrap_or(Direction::Right),
             run,
-            direction: Direction::Right,
             queued_directions: VecDeque::new(),
             phase: RunningPhase::Active,
             replay_timer: 0.0,
             accumulator: 0.0,
             tick_seconds: SIM_TICK_SECONDS,
-            foods: Vec::new(),
             spawn_seed: 0,
+            initial_seed: 0,
+            pause_cursor: 0,
             replay_path: Vec::new(),
             pointer_idle_anchor: None,
             pointer_idle_elapsed: 0.0,
             idle_grace_timer: 0.0,
+            blink_clock: 0.0,
+            reposition_trail: None,
         }
     }
 }
 
+impl RunningState {
+    /// Base tick length adjusted for the speed curve and speed-up food.
+    fn effective_tick_seconds(&self) -> f32 {
+        self.tick_seconds * 100.0 / self.run.speed_percent() as f32
+    }
+
+    /// Clock left in a timed run, in seconds at the current pace.
+    fn time_remaining_seconds(&self) -> Option<f32> {
+        self.run
+            .time_remaining_ticks
+            .map(|ticks| ticks as f32 * self.effective_tick_seconds())
+    }
+}
+
 #[derive(Debug, Clone)]
 struct RunSummary {
     mode: GameMode,
@@ -185,7 +355,20 @@ struct RunSummary {
     survival_ticks: u64,
     food_eaten: u64,
     growth_units: u64,
+    repositions: u64,
     loadout_summary: String,
+    abandoned: bool,
+    won: bool,
+    perfect: bool,
+    timed_out: bool,
+    out_of_moves: bool,
+    /// Campaign stage cleared by the run.
+    cleared_stage: Option<usize>,
+    /// Stars earned if the run solved a puzzle.
+    puzzle_stars: Option<u8>,
+    difficulty: Difficulty,
+    /// Leaderboard id, if the run was submitted.
+    run_id: Option<u64>,
 }
 
 pub struct SnakeGuiApp {
@@ -193,12 +376,43 @@ pub struct SnakeGuiApp {
     screen: ScreenState,
     main_menu_cursor: usize,
     mode_cursor: usize,
+    puzzle_cursor: usize,
+    campaign_cursor: usize,
+    /// Rules being edited on the custom builder; saved or not.
+    custom_draft: CustomRules,
+    custom_cursor: usize,
     leaderboard_mode_cursor: usize,
+    /// Difficulty, loadout and board filters; the date comes from
+    /// `leaderboard_date_range`.
+    leaderboard_filter: LeaderboardFilter,
+    leaderboard_date_range: usize,
+    leaderboard_focus: usize,
+    leaderboard_page: usize,
+    /// Experimental only: one row per loadout, its personal best.
+    leaderboard_best_per_loadout: bool,
+    /// Run id of the run just finished, when arriving from the summary.
+    leaderboard_highlight: Option<u64>,
+    leaderboard_file_action: usize,
+    /// Where leaderboard exports are written and imports read from.
+    export_dir: PathBuf,
+    /// Row on the profile screen; the last row creates a new profile.
+    profile_cursor: usize,
+    /// Name typed so far on the name entry screen.
+    name_draft: String,
     loadout_state: LoadoutState,
     running: Option<RunningState>,
     summary: Option<RunSummary>,
     message: Option<String>,
+    settings_return_screen: ScreenState,
+    settings_cursor: usize,
+    settings_scroll: usize,
+    settings_mode_cursor: usize,
     pointer_last_position: Option<Vec2>,
+    pointer_primary_down: bool,
+    pointer_primary_pressed: bool,
+    swipe_tracker: SwipeTracker,
+    effects: Effects,
+    audio: SoundManager,
     pointer_focus_target: Option<PointerFocusTarget>,
     pointer_focus_dwell: f32,
     pointer_focus_armed: bool,
@@ -206,23 +420,58 @@ pub struct SnakeGuiApp {
     pointer_back_armed: bool,
 }
 
+impl Default for SnakeGuiApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SnakeGuiApp {
     pub fn new() -> Self {
         Self::with_profile(Profile::default())
     }
 
     pub fn with_profile(profile: Profile) -> Self {
+        Self::with_engine(GameEngine::new(profile))
+    }
+
+    /// An app over `engine`, whose mode registry drives every menu.
+    pub fn with_engine(engine: GameEngine) -> Self {
+        let custom_draft = new_custom_draft(&engine.profile);
         Self {
-            engine: GameEngine::new(profile),
+            engine,
             screen: ScreenState::MainMenu,
             main_menu_cursor: 0,
             mode_cursor: 0,
+            puzzle_cursor: 0,
+            campaign_cursor: 0,
+            custom_draft,
+            custom_cursor: 0,
             leaderboard_mode_cursor: 0,
+            leaderboard_filter: LeaderboardFilter::default(),
+            leaderboard_date_range: 0,
+            leaderboard_focus: 0,
+            leaderboard_page: 0,
+            leaderboard_best_per_loadout: false,
+            leaderboard_highlight: None,
+            leaderboard_file_action: 0,
+            export_dir: PathBuf::from("."),
+            profile_cursor: 0,
+            name_draft: String::new(),
             loadout_state: LoadoutState::default(),
             running: None,
             summary: None,
             message: None,
+            settings_return_screen: ScreenState::MainMenu,
+            settings_cursor: 0,
+            settings_scroll: 0,
+            settings_mode_cursor: 0,
             pointer_last_position: None,
+            pointer_primary_down: false,
+            pointer_primary_pressed: false,
+            swipe_tracker: SwipeTracker::default(),
+            effects: Effects::default(),
+            audio: SoundManager::default(),
             pointer_focus_target: None,
             pointer_focus_dwell: 0.0,
             pointer_focus_armed: false,
@@ -235,13 +484,36 @@ impl SnakeGuiApp {
         self.poll_keyboard_commands();
         let (mx, my) = mouse_position();
         let (_, wheel_y) = mouse_wheel();
+        self.pointer_primary_down = is_mouse_button_down(MouseButton::Left);
+        self.pointer_primary_pressed = is_mouse_button_pressed(MouseButton::Left);
         self.apply_pointer_input(dt, vec2(mx, my), wheel_y);
         if self.screen == ScreenState::Running {
             self.update_running(dt);
         }
+        self.effects.update(dt);
+        self.sync_music();
     }
 
     fn poll_keyboard_commands(&mut self) {
+        // Drain typed characters every frame so none carry over into name entry.
+        while let Some(typed) = get_char_pressed() {
+            if self.screen == ScreenState::NameEntry {
+                self.type_name_char(typed);
+            }
+        }
+        if self.screen == ScreenState::NameEntry {
+            // Letters and Space are text here, so only a few keys act as commands.
+            if is_key_pressed(KeyCode::Backspace) {
+                self.name_draft.pop();
+            }
+            if is_key_pressed(KeyCode::Enter) {
+                self.apply_command(UiCommand::Confirm);
+            }
+            if is_key_pressed(KeyCode::Escape) {
+                self.apply_command(UiCommand::Back);
+            }
+            return;
+        }
         if is_key_pressed(KeyCode::Up) || is_key_pressed(KeyCode::W) {
             self.apply_command(UiCommand::Up);
         }
@@ -263,9 +535,21 @@ impl SnakeGuiApp {
     }
 
     fn apply_command(&mut self, command: UiCommand) {
+        if self.screen != ScreenState::Running || self.is_paused() {
+            let cue = match command {
+                UiCommand::Confirm | UiCommand::Back => SoundEvent::MenuConfirm,
+                _ => SoundEvent::MenuMove,
+            };
+            self.audio.play(cue, &self.engine.profile.audio_volume);
+        }
         match self.screen {
+            ScreenState::ProfileSelect => self.apply_profile_select_command(command),
+            ScreenState::NameEntry => self.apply_name_entry_command(command),
             ScreenState::MainMenu => self.apply_main_menu_command(command),
             ScreenState::ModeSelect => self.apply_mode_select_command(command),
+            ScreenState::PuzzleSelect => self.apply_puzzle_select_command(command),
+            ScreenState::CampaignMap => self.apply_campaign_command(command),
+            ScreenState::CustomBuilder => self.apply_custom_command(command),
             ScreenState::Loadout => self.apply_loadout_command(command),
             ScreenState::Running => self.apply_running_command(command),
             ScreenState::Summary => self.apply_summary_command(command),
@@ -290,28 +574,114 @@ impl SnakeGuiApp {
                 }
                 1 => {
                     self.leaderboard_mode_cursor = 0;
-                    self.screen = ScreenState::Leaderboard;
+                    self.open_leaderboard(None);
                 }
-                2 => self.screen = ScreenState::Settings,
+                2 => self.open_settings(ScreenState::MainMenu),
+                3 => self.open_profile_select(),
                 _ => {}
             },
             UiCommand::Back | UiCommand::Left | UiCommand::Right => {}
         }
     }
 
+    fn open_profile_select(&mut self) {
+        let active = &self.engine.profile.name;
+        self.profile_cursor = self
+            .engine
+            .profile_names()
+            .iter()
+            .position(|name| name == active)
+            .unwrap_or(0);
+        self.screen = ScreenState::ProfileSelect;
+    }
+
+    fn apply_profile_select_command(&mut self, command: UiCommand) {
+        let names: Vec<String> = self
+            .engine
+            .profile_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let rows = names.len() + 1;
+        match command {
+            UiCommand::Up => self.profile_cursor = cycle_index(self.profile_cursor, -1, rows),
+            UiCommand::Down => self.profile_cursor = cycle_index(self.profile_cursor, 1, rows),
+            UiCommand::Confirm => match names.get(self.profile_cursor) {
+                Some(name) => match self.engine.switch_profile(name) {
+                    Ok(()) => self.enter_profile(),
+                    Err(err) => self.message = Some(err),
+                },
+                None if names.len() >= MAX_PROFILES => {
+                    self.message = Some(format!("at most {MAX_PROFILES} profiles are supported"));
+                }
+                None => {
+                    self.name_draft.clear();
+                    self.screen = ScreenState::NameEntry;
+                }
+            },
+            UiCommand::Back => self.screen = ScreenState::MainMenu,
+            UiCommand::Left | UiCommand::Right => {}
+        }
+    }
+
+    fn apply_name_entry_command(&mut self, command: UiCommand) {
+        match command {
+            UiCommand::Confirm => match self.engine.create_profile(&self.name_draft) {
+                Ok(()) => self.enter_profile(),
+                Err(err) => self.message = Some(err),
+            },
+            UiCommand::Left => {
+                self.name_draft.pop();
+            }
+            UiCommand::Back => self.screen = ScreenState::ProfileSelect,
+            UiCommand::Up | UiCommand::Down | UiCommand::Right => {}
+        }
+    }
+
+    fn type_name_char(&mut self, typed: char) {
+        if !typed.is_control() && self.name_draft.chars().count() < MAX_PROFILE_NAME_LEN {
+            self.name_draft.push(typed);
+        }
+    }
+
+    /// Resets per-profile screen state after switching to another profile.
+    fn enter_profile(&mut self) {
+        self.custom_draft = new_custom_draft(&self.engine.profile);
+        self.summary = None;
+        self.leaderboard_highlight = None;
+        self.main_menu_cursor = 0;
+        self.screen = ScreenState::MainMenu;
+    }
+
     fn apply_mode_select_command(&mut self, command: UiCommand) {
         match command {
-            UiCommand::Left | UiCommand::Up => {
-                self.mode_cursor = cycle_index(self.mode_cursor, -1, MODES.len())
+            UiCommand::Up => {
+                self.mode_cursor = cycle_index(self.mode_cursor, -1, self.modes().len())
+            }
+            UiCommand::Down => {
+                self.mode_cursor = cycle_index(self.mode_cursor, 1, self.modes().len())
             }
-            UiCommand::Right | UiCommand::Down => {
-                self.mode_cursor = cycle_index(self.mode_cursor, 1, MODES.len())
+            UiCommand::Left | UiCommand::Right => {
+                let delta = if command == UiCommand::Left { -1 } else { 1 };
+                let current = &mut self.engine.profile.difficulty;
+                let index = Difficulty::ALL
+                    .iter()
+                    .position(|d| d == current)
+                    .unwrap_or(0);
+                *current = Difficulty::ALL[cycle_index(index, delta, Difficulty::ALL.len())];
             }
             UiCommand::Confirm => {
-                let mode = MODES[self.mode_cursor];
-                if mode == GameMode::Experimental {
+                let mode = self.modes()[self.mode_cursor];
+                if mode.requires_loadout() {
                     self.loadout_state = self.default_loadout_state();
                     self.screen = ScreenState::Loadout;
+                } else if mode == GameMode::PUZZLE {
+                    self.screen = ScreenState::PuzzleSelect;
+                } else if mode == GameMode::CAMPAIGN {
+                    self.open_campaign_map();
+                } else if mode == GameMode::CUSTOM {
+                    self.custom_cursor = 0;
+                    self.screen = ScreenState::CustomBuilder;
                 } else {
                     self.start_mode(mode, None);
                 }
@@ -320,6 +690,120 @@ impl SnakeGuiApp {
         }
     }
 
+    fn apply_puzzle_select_command(&mut self, command: UiCommand) {
+        match command {
+            UiCommand::Up | UiCommand::Left => {
+                self.puzzle_cursor = cycle_index(self.puzzle_cursor, -1, PUZZLES.len())
+            }
+            UiCommand::Down | UiCommand::Right => {
+                self.puzzle_cursor = cycle_index(self.puzzle_cursor, 1, PUZZLES.len())
+            }
+            UiCommand::Confirm => self.start_mode(GameMode::PUZZLE, None),
+            UiCommand::Back => self.screen = ScreenState::ModeSelect,
+        }
+    }
+
+    /// Opens the map on the furthest stage the player can play.
+    fn open_campaign_map(&mut self) {
+        let profile = &self.engine.profile;
+        self.campaign_cursor = (0..STAGES.len())
+            .rev()
+            .find(|index| profile.campaign_stage_unlocked(*index))
+            .unwrap_or(0);
+        self.screen = ScreenState::CampaignMap;
+    }
+
+    fn apply_campaign_command(&mut self, command: UiCommand) {
+        match command {
+            UiCommand::Up | UiCommand::Left => {
+                self.campaign_cursor = cycle_index(self.campaign_cursor, -1, STAGES.len())
+            }
+            UiCommand::Down | UiCommand::Right => {
+                self.campaign_cursor = cycle_index(self.campaign_cursor, 1, STAGES.len())
+            }
+            UiCommand::Confirm => self.start_mode(GameMode::CAMPAIGN, None),
+            UiCommand::Back => self.screen = ScreenState::ModeSelect,
+        }
+    }
+
+    fn custom_row_count(&self) -> usize {
+        CUSTOM_FIXED_ROWS + self.tool_ids().len() + 2
+    }
+
+    fn apply_custom_command(&mut self, command: UiCommand) {
+        let rows = self.custom_row_count();
+        match command {
+            UiCommand::Up => self.custom_cursor = cycle_index(self.custom_cursor, -1, rows),
+            UiCommand::Down => self.custom_cursor = cycle_index(self.custom_cursor, 1, rows),
+            UiCommand::Left => self.adjust_custom_rule(-1),
+            UiCommand::Right => self.adjust_custom_rule(1),
+            UiCommand::Confirm if self.custom_cursor == rows - 2 => {
+                self.engine
+                    .profile
+                    .save_custom_preset(self.custom_draft.clone());
+                self.message = Some(format!("Saved preset {}", self.custom_draft.name));
+            }
+            UiCommand::Confirm if self.custom_cursor == rows - 1 => {
+                self.start_mode(GameMode::CUSTOM, None)
+            }
+            UiCommand::Confirm => self.adjust_custom_rule(1),
+            UiCommand::Back => self.screen = ScreenState::ModeSelect,
+        }
+    }
+
+    fn adjust_custom_rule(&mut self, delta: i32) {
+        let presets = &self.engine.profile.custom_presets;
+        let draft = &mut self.custom_draft;
+        let weights = &mut draft.weights;
+        match self.custom_cursor {
+            // Option 0 starts a new unsaved preset; the rest load saved ones.
+            0 => {
+                let current = presets
+                    .iter()
+                    .position(|preset| preset.name == draft.name)
+                    .map_or(0, |index| index + 1);
+                *draft = match cycle_index(current, delta, presets.len() + 1) {
+                    0 => new_custom_draft(&self.engine.profile),
+                    index => presets[index - 1].clone(),
+                };
+            }
+            1 => {
+                draft.collision = match draft.collision {
+                    CollisionOutcome::Die => CollisionOutcome::Reposition,
+                    CollisionOutcome::Reposition => CollisionOutcome::Die,
+                }
+            }
+            2 => draft.wraps = !draft.wraps,
+            3 => {
+                let next = cycle_index(draft.food_option(), delta, FOOD_OPTIONS.len());
+                draft.food_rules = FOOD_OPTIONS[next].1;
+            }
+            4 => {
+                let next = cycle_index(draft.speed_option(), delta, SPEED_OPTIONS.len());
+                draft.speed_curve = SPEED_OPTIONS[next].1;
+            }
+            5 => weights.per_food = step_setting(weights.per_food, delta, 5, 0, MAX_WEIGHT),
+            6 => {
+                weights.per_ten_ticks = step_setting(weights.per_ten_ticks, delta, 1, 0, MAX_WEIGHT)
+            }
+            7 => {
+                weights.per_speed_point =
+                    step_setting(weights.per_speed_point, delta, 1, 0, MAX_WEIGHT)
+            }
+            row => {
+                let ids = self.tool_ids();
+                let Some(tool) = ids.get(row - CUSTOM_FIXED_ROWS) else {
+                    return;
+                };
+                if !self.engine.profile.unlocked_tool_ids.contains(tool) {
+                    self.message = Some(format!("tool not unlocked: {tool}"));
+                } else if !self.custom_draft.tools.remove(tool) {
+                    self.custom_draft.tools.insert(tool.clone());
+                }
+            }
+        }
+    }
+
     fn apply_loadout_command(&mut self, command: UiCommand) {
         let all_tools = self.tool_ids();
         if all_tools.is_empty() {
@@ -348,54 +832,335 @@ impl SnakeGuiApp {
             }
             UiCommand::Confirm => {
                 let selected = self.selected_loadout_tool_ids();
-                self.start_mode(GameMode::Experimental, Some(selected));
+                self.start_mode(self.modes()[self.mode_cursor], Some(selected));
             }
             UiCommand::Back => self.screen = ScreenState::ModeSelect,
         }
     }
 
     fn apply_running_command(&mut self, command: UiCommand) {
+        let Some(phase) = self.running.as_ref().map(|state| state.phase) else {
+            return;
+        };
+        if phase == RunningPhase::Paused {
+            self.apply_pause_command(command);
+            return;
+        }
+
         match command {
             UiCommand::Up => self.enqueue_running_direction(Direction::Up),
             UiCommand::Down => self.enqueue_running_direction(Direction::Down),
             UiCommand::Left => self.enqueue_running_direction(Direction::Left),
             UiCommand::Right => self.enqueue_running_direction(Direction::Right),
-            UiCommand::Back => self.complete_running_session(),
+            UiCommand::Back if phase == RunningPhase::Replay => self.complete_running_session(),
+            UiCommand::Back => self.pause_running(),
             UiCommand::Confirm => {}
         }
     }
 
+    fn apply_pause_command(&mut self, command: UiCommand) {
+        let Some(state) = self.running.as_mut() else {
+            return;
+        };
+        match command {
+            UiCommand::Up | UiCommand::Left => {
+                state.pause_cursor = cycle_index(state.pause_cursor, -1, PAUSE_MENU_ITEMS.len())
+            }
+            UiCommand::Down | UiCommand::Right => {
+                state.pause_cursor = cycle_index(state.pause_cursor, 1, PAUSE_MENU_ITEMS.len())
+            }
+            UiCommand::Confirm => match state.pause_cursor {
+                0 => self.resume_from_pause(),
+                1 => self.restart_running_session(),
+                2 => self.open_settings(ScreenState::Running),
+                3 => self.abandon_running_session(),
+                _ => {}
+            },
+            UiCommand::Back => self.resume_from_pause(),
+        }
+    }
+
     fn apply_summary_command(&mut self, command: UiCommand) {
         match command {
             UiCommand::Confirm | UiCommand::Back => self.screen = ScreenState::MainMenu,
-            UiCommand::Right => self.screen = ScreenState::Leaderboard,
+            UiCommand::Right => {
+                let run_id = self.summary.as_ref().and_then(|summary| summary.run_id);
+                self.open_leaderboard(run_id);
+            }
             UiCommand::Up | UiCommand::Down | UiCommand::Left => {}
         }
     }
 
+    /// Opens the leaderboard with filters cleared, on the page holding
+    /// `highlight` if it is given.
+    fn open_leaderboard(&mut self, highlight: Option<u64>) {
+        self.leaderboard_filter = LeaderboardFilter::default();
+        self.leaderboard_date_range = 0;
+        self.leaderboard_focus = 0;
+        self.leaderboard_best_per_loadout = false;
+        self.leaderboard_highlight = highlight;
+        self.leaderboard_page = highlight
+            .and_then(|id| {
+                self.leaderboard_rows()
+                    .iter()
+                    .position(|row| row.run_id == id)
+            })
+            .map_or(0, |index| index / LEADERBOARD_PAGE_ROWS);
+        self.screen = ScreenState::Leaderboard;
+    }
+
     fn apply_leaderboard_command(&mut self, command: UiCommand) {
         match command {
-            UiCommand::Left | UiCommand::Up => {
-                self.leaderboard_mode_cursor =
-                    cycle_index(self.leaderboard_mode_cursor, -1, MODES.len())
+            UiCommand::Up => {
+                self.leaderboard_focus =
+                    cycle_index(self.leaderboard_focus, -1, LEADERBOARD_CONTROLS.len())
             }
-            UiCommand::Right | UiCommand::Down => {
-                self.leaderboard_mode_cursor =
-                    cycle_index(self.leaderboard_mode_cursor, 1, MODES.len())
+            UiCommand::Down => {
+                self.leaderboard_focus =
+                    cycle_index(self.leaderboard_focus, 1, LEADERBOARD_CONTROLS.len())
+            }
+            UiCommand::Left => self.adjust_leaderboard_control(-1),
+            UiCommand::Right => self.adjust_leaderboard_control(1),
+            UiCommand::Confirm if LEADERBOARD_CONTROLS[self.leaderboard_focus] == "Files" => {
+                self.run_leaderboard_file_action()
             }
             UiCommand::Confirm | UiCommand::Back => self.screen = ScreenState::MainMenu,
         }
     }
 
+    fn leaderboard_file_path(&self, format: ExportFormat) -> PathBuf {
+        let mode = self.modes()[self.leaderboard_mode_cursor];
+        self.export_dir
+            .join(format!("leaderboard-{}.{}", mode.id(), format.extension()))
+    }
+
+    /// Exports the selected mode's board to, or imports it from, its file in
+    /// `export_dir`, reporting the outcome as a message.
+    fn run_leaderboard_file_action(&mut self) {
+        let mode = self.modes()[self.leaderboard_mode_cursor];
+        let (_, format, import) = LEADERBOARD_FILE_ACTIONS[self.leaderboard_file_action];
+        let path = self.leaderboard_file_path(format);
+        let outcome = if import {
+            fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|text| self.engine.import_leaderboard(&text, format))
+                .map(|report| {
+                    let mut message = format!(
+                        "Imported {} rows, skipped {} duplicates, rejected {}",
+                        report.added,
+                        report.duplicates,
+                        report.rejected.len()
+                    );
+                    if let Some(first) = report.rejected.first() {
+                        message.push_str(&format!(" ({first})"));
+                    }
+                    message
+                })
+        } else {
+            let text = self.engine.export_leaderboard(mode, format);
+            fs::write(&path, text)
+                .map(|()| {
+                    format!(
+                        "Exported {} rows to {}",
+                        self.engine.leaderboards.rows(mode).len(),
+                        path.display()
+                    )
+                })
+                .map_err(|err| err.to_string())
+        };
+        self.message = Some(outcome.unwrap_or_else(|err| format!("{}: {err}", path.display())));
+    }
+
+    fn adjust_leaderboard_control(&mut self, delta: i32) {
+        let modes = self.modes();
+        let mode = modes[self.leaderboard_mode_cursor];
+        let mode_count = modes.len();
+        let boards = &self.engine.leaderboards;
+        let filter = &mut self.leaderboard_filter;
+        match self.leaderboard_focus {
+            0 => {
+                self.leaderboard_mode_cursor =
+                    cycle_index(self.leaderboard_mode_cursor, delta, mode_count);
+                // Loadouts and board sizes differ between modes.
+                filter.loadout = None;
+                filter.board = None;
+                self.leaderboard_best_per_loadout = false;
+            }
+            1 => filter.difficulty = cycle_choice(&Difficulty::ALL, filter.difficulty, delta),
+            2 => {
+                filter.loadout = cycle_choice(&boards.loadouts(mode), filter.loadout.take(), delta)
+            }
+            3 => filter.board = cycle_choice(&boards.boards(mode), filter.board, delta),
+            4 => {
+                self.leaderboard_date_range = cycle_index(
+                    self.leaderboard_date_range,
+                    delta,
+                    LEADERBOARD_DATE_RANGES.len(),
+                )
+            }
+            5 if mode == GameMode::EXPERIMENTAL => {
+                self.leaderboard_best_per_loadout = !self.leaderboard_best_per_loadout
+            }
+            6 => {
+                self.leaderboard_file_action = cycle_index(
+                    self.leaderboard_file_action,
+                    delta,
+                    LEADERBOARD_FILE_ACTIONS.len(),
+                );
+                return;
+            }
+            7 => {
+                self.leaderboard_page =
+                    cycle_index(self.leaderboard_page, delta, self.leaderboard_page_count());
+                return;
+            }
+            _ => {}
+        }
+        self.leaderboard_page = 0;
+    }
+
+    /// Rows of the selected mode after filters, in rank order.
+    fn leaderboard_rows(&self) -> Vec<&LeaderboardEntry> {
+        let mode = self.modes()[self.leaderboard_mode_cursor];
+        let mut filter = self.leaderboard_filter.clone();
+        filter.recorded_from = LEADERBOARD_DATE_RANGES[self.leaderboard_date_range]
+            .1
+            .map(|reach| unix_now().saturating_sub(reach));
+        let boards = &self.engine.leaderboards;
+        if self.leaderboard_best_per_loadout && mode == GameMode::EXPERIMENTAL {
+            boards.best_per_loadout(mode, &filter)
+        } else {
+            boards.rows_matching(mode, &filter).collect()
+        }
+    }
+
+    fn leaderboard_page_count(&self) -> usize {
+        self.leaderboard_rows()
+            .len()
+            .div_ceil(LEADERBOARD_PAGE_ROWS)
+            .max(1)
+    }
+
     fn apply_settings_command(&mut self, command: UiCommand) {
         match command {
-            UiCommand::Left | UiCommand::Right | UiCommand::Confirm => {
-                let next = !self.engine.profile.replay_on_death;
+            UiCommand::Up => {
+                self.settings_cursor = cycle_index(self.settings_cursor, -1, SETTINGS_ROW_COUNT);
+                self.scroll_settings_to_cursor();
+            }
+            UiCommand::Down => {
+                self.settings_cursor = cycle_index(self.settings_cursor, 1, SETTINGS_ROW_COUNT);
+                self.scroll_settings_to_cursor();
+            }
+            UiCommand::Left => self.adjust_setting(-1),
+            UiCommand::Right | UiCommand::Confirm => self.adjust_setting(1),
+            UiCommand::Back => self.screen = self.settings_return_screen,
+        }
+    }
+
+    fn scroll_settings_to_cursor(&mut self) {
+        if self.settings_cursor < self.settings_scroll {
+            self.settings_scroll = self.settings_cursor;
+        } else if self.settings_cursor >= self.settings_scroll + SETTINGS_VISIBLE_ROWS {
+            self.settings_scroll = self.settings_cursor + 1 - SETTINGS_VISIBLE_ROWS;
+        }
+    }
+
+    fn adjust_setting(&mut self, delta: i32) {
+        let mode = self.modes()[self.settings_mode_cursor];
+        let profile = &mut self.engine.profile;
+        let tuning = &mut profile.pointer_tuning;
+        let volume = &mut profile.audio_volume;
+        match self.settings_cursor {
+            0 => {
+                let next = !profile.replay_on_death;
                 self.engine.enable_replay(next);
             }
-            UiCommand::Back => self.screen = ScreenState::MainMenu,
-            UiCommand::Up | UiCommand::Down => {}
+            1 => {
+                self.settings_mode_cursor =
+                    cycle_index(self.settings_mode_cursor, delta, self.modes().len())
+            }
+            2 => {
+                let current = PointerControl::ALL
+                    .iter()
+                    .position(|control| *control == profile.pointer_control(mode))
+                    .unwrap_or(0);
+                let next = cycle_index(current, delta, PointerControl::ALL.len());
+                profile.set_pointer_control(mode, PointerControl::ALL[next]);
+            }
+            3 => tuning.idle_pause_ms = step_setting(tuning.idle_pause_ms, delta, 10, 10, 2_000),
+            4 => tuning.displacement_px = step_setting(tuning.displacement_px, delta, 1, 1, 24),
+            5 => tuning.dwell_ms = step_setting(tuning.dwell_ms, delta, 50, 150, 2_000),
+            6 => tuning.idle_grace_ms = step_setting(tuning.idle_grace_ms, delta, 50, 0, 2_000),
+            7 => tuning.swipe_min_px = step_setting(tuning.swipe_min_px, delta, 5, 10, 200),
+            8 => {
+                tuning.swipe_min_px_per_second =
+                    step_setting(tuning.swipe_min_px_per_second, delta, 50, 50, 3_000)
+            }
+            9 => profile.show_dpad = !profile.show_dpad,
+            10 => {
+                profile.snake_render = match profile.snake_render {
+                    SnakeRenderStyle::Smooth => SnakeRenderStyle::Classic,
+                    SnakeRenderStyle::Classic => SnakeRenderStyle::Smooth,
+                }
+            }
+            11 => {
+                let current = EffectsIntensity::ALL
+                    .iter()
+                    .position(|intensity| *intensity == profile.effects_intensity)
+                    .unwrap_or(0);
+                profile.effects_intensity =
+                    EffectsIntensity::ALL[cycle_index(current, delta, EffectsIntensity::ALL.len())];
+            }
+            12 => volume.master_percent = step_setting(volume.master_percent, delta, 10, 0, 100),
+            13 => volume.sfx_percent = step_setting(volume.sfx_percent, delta, 10, 0, 100),
+            14 => volume.music_percent = step_setting(volume.music_percent, delta, 10, 0, 100),
+            15 => {
+                let current = ThemeId::ALL
+                    .iter()
+                    .position(|theme| *theme == profile.theme)
+                    .unwrap_or(0);
+                profile.theme = ThemeId::ALL[cycle_index(current, delta, ThemeId::ALL.len())];
+            }
+            _ => {}
+        }
+    }
+
+    fn open_settings(&mut self, return_screen: ScreenState) {
+        self.settings_return_screen = return_screen;
+        self.settings_cursor = 0;
+        self.settings_scroll = 0;
+        if let Some(state) = self.running.as_ref() {
+            self.settings_mode_cursor = self.mode_index(state.run.mode);
         }
+        self.screen = ScreenState::Settings;
+    }
+
+    /// Keeps the current run's track playing, including while its pause
+    /// menu has Settings open; menus and summaries are silent.
+    fn sync_music(&mut self) {
+        let track = self
+            .running
+            .as_ref()
+            .filter(|_| matches!(self.screen, ScreenState::Running | ScreenState::Settings))
+            .map(|state| state.run.mode);
+        self.audio
+            .sync_music(track, &self.engine.profile.audio_volume);
+    }
+
+    fn theme(&self) -> Theme {
+        Theme::for_id(self.engine.profile.theme)
+    }
+
+    fn pointer_tuning(&self) -> PointerTuning {
+        self.engine.profile.pointer_tuning
+    }
+
+    fn modes(&self) -> &[GameMode] {
+        self.engine.modes.list()
+    }
+
+    fn mode_index(&self, mode: GameMode) -> usize {
+        self.engine.modes.index_of(mode)
     }
 
     fn tool_ids(&self) -> Vec<String> {
@@ -458,14 +1223,22 @@ impl SnakeGuiApp {
     }
 
     fn apply_pointer_input(&mut self, dt: f32, pointer_position: Vec2, wheel_y: f32) {
+        let pointer_position = Layout::current().to_design(pointer_position);
         let pointer_delta = self
             .pointer_last_position
             .map(|last| pointer_position - last)
             .unwrap_or(Vec2::ZERO);
         self.pointer_last_position = Some(pointer_position);
+        let gesture = self.swipe_tracker.update(
+            dt,
+            pointer_position,
+            self.pointer_primary_down,
+            self.pointer_primary_pressed,
+            self.pointer_tuning(),
+        );
 
-        if self.screen == ScreenState::Running {
-            self.apply_pointer_running(dt, pointer_position, pointer_delta);
+        if self.screen == ScreenState::Running && !self.is_paused() {
+            self.apply_pointer_running(dt, pointer_position, pointer_delta, gesture);
             self.pointer_focus_target = None;
             self.pointer_focus_dwell = 0.0;
             self.pointer_focus_armed = false;
@@ -474,22 +1247,90 @@ impl SnakeGuiApp {
             return;
         }
 
-        self.apply_pointer_menu(dt, pointer_position, pointer_delta, wheel_y);
+        self.apply_pointer_menu(dt, pointer_position, pointer_delta, wheel_y, gesture);
+    }
+
+    fn apply_pointer_running(
+        &mut self,
+        dt: f32,
+        pointer_position: Vec2,
+        pointer_delta: Vec2,
+        gesture: Option<PointerGesture>,
+    ) {
+        let Some(mode) = self.running.as_ref().map(|state| state.run.mode) else {
+            return;
+        };
+        if self.engine.profile.show_dpad
+            && let Some(direction) = dpad_button_at(pointer_position)
+        {
+            // The d-pad sits outside the board, so it must not count as idling.
+            if self.pointer_primary_pressed {
+                self.enqueue_running_direction(direction);
+            }
+            return;
+        }
+
+        match self.engine.profile.pointer_control(mode) {
+            PointerControl::Off => {}
+            PointerControl::HoverSteer => {
+                self.apply_pointer_hover_steer(dt, pointer_position, pointer_delta)
+            }
+            PointerControl::DragSteer => {
+                self.apply_pointer_drag_steer(pointer_position, pointer_delta)
+            }
+            PointerControl::ClickToTurn => self.apply_pointer_click_to_turn(pointer_position),
+            PointerControl::Swipe => {
+                if let Some(PointerGesture::Swipe(direction)) = gesture {
+                    self.enqueue_running_direction(direction);
+                }
+            }
+        }
+    }
+
+    fn apply_pointer_drag_steer(&mut self, pointer_position: Vec2, pointer_delta: Vec2) {
+        if !self.pointer_primary_down {
+            return;
+        }
+        let threshold = self.pointer_tuning().displacement_threshold();
+        let Some(state) = self.running.as_ref() else {
+            return;
+        };
+        let direction = direction_toward_pointer(state, pointer_position)
+            .or_else(|| direction_from_delta(pointer_delta, threshold));
+        if let Some(direction) = direction {
+            self.enqueue_running_direction(direction);
+        }
+    }
+
+    fn apply_pointer_click_to_turn(&mut self, pointer_position: Vec2) {
+        if !self.pointer_primary_pressed {
+            return;
+        }
+        let direction = self
+            .running
+            .as_ref()
+            .and_then(|state| direction_toward_pointer(state, pointer_position));
+        if let Some(direction) = direction {
+            self.enqueue_running_direction(direction);
+        }
     }
 
-    fn apply_pointer_running(&mut self, dt: f32, pointer_position: Vec2, pointer_delta: Vec2) {
+    fn apply_pointer_hover_steer(&mut self, dt: f32, pointer_position: Vec2, pointer_delta: Vec2) {
+        let tuning = self.pointer_tuning();
+        let threshold = tuning.displacement_threshold();
         let mut resume_due_to_pointer = false;
         let mut pointer_direction = None;
         let mut entered_idle_pause = false;
 
         if let Some(state) = self.running.as_mut() {
             match state.phase {
-                RunningPhase::Replay => return,
+                RunningPhase::Replay | RunningPhase::Paused => return,
                 RunningPhase::PointerIdlePause => {
                     let anchor = state.pointer_idle_anchor.unwrap_or(pointer_position);
-                    if pointer_position.distance(anchor) > POINTER_DISPLACEMENT_THRESHOLD {
+                    if pointer_position.distance(anchor) > threshold {
                         resume_due_to_pointer = true;
-                        pointer_direction = direction_from_delta(pointer_position - anchor);
+                        pointer_direction =
+                            direction_from_delta(pointer_position - anchor, threshold);
                     }
                 }
                 RunningPhase::Active => {
@@ -501,7 +1342,7 @@ impl SnakeGuiApp {
                         direction_toward_pointer(state, pointer_position).is_some();
                     if let Some(direction) = direction_toward_pointer(state, pointer_position) {
                         pointer_direction = Some(direction);
-                    } else if let Some(direction) = direction_from_delta(pointer_delta) {
+                    } else if let Some(direction) = direction_from_delta(pointer_delta, threshold) {
                         pointer_direction = Some(direction);
                     }
                     if state.idle_grace_timer > 0.0 {
@@ -517,9 +1358,9 @@ impl SnakeGuiApp {
                         state.pointer_idle_elapsed = 0.0;
                     } else {
                         let anchor = state.pointer_idle_anchor.unwrap_or(pointer_position);
-                        if pointer_position.distance(anchor) <= POINTER_DISPLACEMENT_THRESHOLD {
+                        if pointer_position.distance(anchor) <= threshold {
                             state.pointer_idle_elapsed += dt;
-                            if state.pointer_idle_elapsed >= POINTER_IDLE_SECONDS_OUTSIDE_BOARD {
+                            if state.pointer_idle_elapsed >= tuning.idle_pause_seconds() {
                                 state.phase = RunningPhase::PointerIdlePause;
                                 state.pointer_idle_anchor = Some(pointer_position);
                                 state.pointer_idle_elapsed = 0.0;
@@ -537,10 +1378,8 @@ impl SnakeGuiApp {
         if resume_due_to_pointer {
             self.resume_from_pointer_idle_pause(Some(pointer_position));
         }
-        if !entered_idle_pause {
-            if let Some(direction) = pointer_direction {
-                self.enqueue_running_direction(direction);
-            }
+        if !entered_idle_pause && let Some(direction) = pointer_direction {
+            self.enqueue_running_direction(direction);
         }
     }
 
@@ -550,6 +1389,7 @@ impl SnakeGuiApp {
         pointer_position: Vec2,
         pointer_delta: Vec2,
         wheel_y: f32,
+        gesture: Option<PointerGesture>,
     ) {
         if self.supports_scroll_navigation() {
             if wheel_y > 0.0 {
@@ -558,20 +1398,26 @@ impl SnakeGuiApp {
                 self.apply_command(UiCommand::Down);
             }
         }
+        if let Some(PointerGesture::Swipe(direction)) = gesture {
+            self.apply_command(direction.command());
+        }
+        let tapped = gesture == Some(PointerGesture::Tap);
 
+        let tuning = self.pointer_tuning();
+        let threshold = tuning.displacement_threshold();
         let focus = self.pointer_focus_target(pointer_position);
         if let Some(target) = focus {
             self.apply_pointer_focus(target);
-            if self.pointer_focus_target == Some(target)
-                && pointer_delta.length() <= POINTER_DISPLACEMENT_THRESHOLD
-            {
+            if self.pointer_focus_target == Some(target) && pointer_delta.length() <= threshold {
                 self.pointer_focus_dwell += dt;
             } else {
                 self.pointer_focus_dwell = dt;
                 self.pointer_focus_target = Some(target);
                 self.pointer_focus_armed = false;
             }
-            if self.pointer_focus_dwell >= POINTER_DWELL_SECONDS && !self.pointer_focus_armed {
+            let dwell_elapsed =
+                self.pointer_focus_dwell >= tuning.dwell_seconds() && !self.pointer_focus_armed;
+            if tapped || dwell_elapsed {
                 self.apply_command(UiCommand::Confirm);
                 self.pointer_focus_armed = true;
             }
@@ -582,13 +1428,15 @@ impl SnakeGuiApp {
         }
 
         if self.is_menu_oriented_screen() && pointer_in_back_hotzone(pointer_position) {
-            if pointer_delta.length() <= POINTER_DISPLACEMENT_THRESHOLD {
+            if pointer_delta.length() <= threshold {
                 self.pointer_back_dwell += dt;
             } else {
                 self.pointer_back_dwell = dt;
                 self.pointer_back_armed = false;
             }
-            if self.pointer_back_dwell >= POINTER_DWELL_SECONDS && !self.pointer_back_armed {
+            let dwell_elapsed =
+                self.pointer_back_dwell >= tuning.dwell_seconds() && !self.pointer_back_armed;
+            if tapped || dwell_elapsed {
                 self.apply_command(UiCommand::Back);
                 self.pointer_back_armed = true;
             }
@@ -600,26 +1448,50 @@ impl SnakeGuiApp {
 
     fn apply_pointer_focus(&mut self, target: PointerFocusTarget) {
         match target {
+            PointerFocusTarget::ProfileItem(index) => self.profile_cursor = index,
             PointerFocusTarget::MainMenuItem(index) => self.main_menu_cursor = index,
             PointerFocusTarget::ModeItem(index) => self.mode_cursor = index,
+            PointerFocusTarget::PuzzleItem(index) => self.puzzle_cursor = index,
+            PointerFocusTarget::CampaignStage(index) => self.campaign_cursor = index,
+            PointerFocusTarget::CustomRow(index) => self.custom_cursor = index,
             PointerFocusTarget::LoadoutSlot(index) => self.loadout_state.slot_cursor = index,
-            PointerFocusTarget::SettingsToggle => {}
+            PointerFocusTarget::SettingsRow(index) => self.settings_cursor = index,
+            PointerFocusTarget::PauseItem(index) => {
+                if let Some(state) = self.running.as_mut() {
+                    state.pause_cursor = index;
+                }
+            }
         }
     }
 
     fn pointer_focus_target(&self, pointer_position: Vec2) -> Option<PointerFocusTarget> {
         match self.screen {
+            ScreenState::ProfileSelect => {
+                mode_item_at(pointer_position, self.engine.profile_names().len() + 1)
+                    .map(PointerFocusTarget::ProfileItem)
+            }
             ScreenState::MainMenu => {
                 main_menu_item_at(pointer_position).map(PointerFocusTarget::MainMenuItem)
             }
             ScreenState::ModeSelect => {
-                mode_item_at(pointer_position).map(PointerFocusTarget::ModeItem)
+                mode_item_at(pointer_position, self.modes().len()).map(PointerFocusTarget::ModeItem)
+            }
+            ScreenState::PuzzleSelect => {
+                puzzle_item_at(pointer_position).map(PointerFocusTarget::PuzzleItem)
             }
+            ScreenState::CampaignMap => {
+                campaign_node_at(pointer_position).map(PointerFocusTarget::CampaignStage)
+            }
+            ScreenState::CustomBuilder => (0..self.custom_row_count())
+                .find(|row| layout::settings_row(*row).hit.contains(pointer_position))
+                .map(PointerFocusTarget::CustomRow),
             ScreenState::Loadout => {
                 loadout_slot_at(pointer_position).map(PointerFocusTarget::LoadoutSlot)
             }
-            ScreenState::Settings => {
-                settings_toggle_hit(pointer_position).then_some(PointerFocusTarget::SettingsToggle)
+            ScreenState::Settings => settings_row_at(pointer_position, self.settings_scroll)
+                .map(PointerFocusTarget::SettingsRow),
+            ScreenState::Running if self.is_paused() => {
+                pause_item_at(pointer_position).map(PointerFocusTarget::PauseItem)
             }
             _ => None,
         }
@@ -628,18 +1500,75 @@ impl SnakeGuiApp {
     fn supports_scroll_navigation(&self) -> bool {
         matches!(
             self.screen,
-            ScreenState::MainMenu
+            ScreenState::ProfileSelect
+                | ScreenState::MainMenu
                 | ScreenState::ModeSelect
+                | ScreenState::PuzzleSelect
+                | ScreenState::CampaignMap
+                | ScreenState::CustomBuilder
                 | ScreenState::Loadout
                 | ScreenState::Leaderboard
+                | ScreenState::Settings
         )
     }
 
     fn is_menu_oriented_screen(&self) -> bool {
-        self.screen != ScreenState::Running
+        self.screen != ScreenState::Running || self.is_paused()
     }
 
-    fn resume_from_pointer_idle_pause(&mut self, pointer_position: Option<Vec2>) {
+    fn is_paused(&self) -> bool {
+        self.running
+            .as_ref()
+            .is_some_and(|state| state.phase == RunningPhase::Paused)
+    }
+
+    fn pause_running(&mut self) {
+        let Some(state) = self.running.as_mut() else {
+            return;
+        };
+        if matches!(
+            state.phase,
+            RunningPhase::Active | RunningPhase::PointerIdlePause
+        ) {
+            state.phase = RunningPhase::Paused;
+            state.pause_cursor = 0;
+            state.queued_directions.clear();
+        }
+    }
+
+    fn resume_from_pause(&mut self) {
+        let Some(state) = self.running.as_mut() else {
+            return;
+        };
+        if state.phase != RunningPhase::Paused {
+            return;
+        }
+        state.phase = RunningPhase::Active;
+        state.pointer_idle_anchor = None;
+        state.pointer_idle_elapsed = 0.0;
+        state.idle_grace_timer = self.engine.profile.pointer_tuning.idle_grace_seconds();
+    }
+
+    fn restart_running_session(&mut self) {
+        let Some(state) = self.running.take() else {
+            return;
+        };
+        let requested_loadout = state
+            .run
+            .active_loadout
+            .as_ref()
+            .map(|loadout| loadout.slots.to_vec());
+        self.start_mode_with_seed(state.run.mode, requested_loadout, state.initial_seed);
+    }
+
+    fn abandon_running_session(&mut self) {
+        if let Some(state) = self.running.as_mut() {
+            state.run.abandoned = true;
+        }
+        self.complete_running_session();
+    }
+
+    fn resume_from_pointer_idle_pause(&mut self, pointer_position: Option<Vec2>) {
         let Some(state) = self.running.as_mut() else {
             return;
         };
@@ -648,31 +1577,49 @@ impl SnakeGuiApp {
         }
         state.phase = RunningPhase::Active;
         state.pointer_idle_elapsed = 0.0;
-        state.idle_grace_timer = POINTER_IDLE_GRACE_SECONDS;
+        state.idle_grace_timer = self.engine.profile.pointer_tuning.idle_grace_seconds();
         state.pointer_idle_anchor = pointer_position.or(state.pointer_idle_anchor);
     }
 
     fn start_mode(&mut self, mode: GameMode, requested_loadout: Option<Vec<String>>) {
+        self.start_mode_with_seed(mode, requested_loadout, random_seed());
+    }
+
+    fn start_mode_with_seed(
+        &mut self,
+        mode: GameMode,
+        requested_loadout: Option<Vec<String>>,
+        seed: u64,
+    ) {
         self.message = None;
-        match self.engine.start_run(mode, requested_loadout) {
+        let started = if mode == GameMode::PUZZLE {
+            self.engine.start_puzzle(PUZZLES[self.puzzle_cursor].id)
+        } else if mode == GameMode::CAMPAIGN {
+            self.engine.start_stage(self.campaign_cursor)
+        } else if mode == GameMode::CUSTOM {
+            self.engine.start_custom(&self.custom_draft)
+        } else {
+            self.engine.start_run(mode, requested_loadout)
+        };
+        match started {
             Ok(run) => {
                 let mut running = RunningState::new(run);
-                let seed = random_seed();
-                let (foods, next_seed) = spawn_food_positions(
-                    seed,
-                    &running.run,
-                    &running.foods,
-                    INITIAL_FOOD_COUNT,
-                );
-                running.foods = foods;
-                running.spawn_seed = next_seed;
+                running.initial_seed = seed;
+                running.run.seed_food(seed);
+                running.spawn_seed = lcg_next(seed);
                 self.running = Some(running);
+                self.effects.clear();
                 self.screen = ScreenState::Running;
             }
             Err(err) => {
                 self.message = Some(err);
-                self.screen = if mode == GameMode::Experimental {
+                self.screen = if mode.requires_loadout() {
+                    self.mode_cursor = self.mode_index(mode);
                     ScreenState::Loadout
+                } else if mode == GameMode::CAMPAIGN {
+                    ScreenState::CampaignMap
+                } else if mode == GameMode::CUSTOM {
+                    ScreenState::CustomBuilder
                 } else {
                     ScreenState::ModeSelect
                 };
@@ -681,7 +1628,16 @@ impl SnakeGuiApp {
     }
 
     fn update_running(&mut self, dt: f32) {
-        let Some(phase) = self.running.as_ref().map(|state| state.phase) else {
+        let Some(phase) = self.running.as_mut().map(|state| {
+            state.blink_clock += dt;
+            if let Some(trail) = state.reposition_trail.as_mut() {
+                trail.remaining -= dt;
+                if trail.remaining <= 0.0 {
+                    state.reposition_trail = None;
+                }
+            }
+            state.phase
+        }) else {
             return;
         };
 
@@ -692,10 +1648,10 @@ impl SnakeGuiApp {
                 }
                 loop {
                     let should_step = if let Some(state) = self.running.as_mut() {
-                        if state.phase == RunningPhase::Active
-                            && state.accumulator >= state.tick_seconds
+                        let tick_seconds = state.effective_tick_seconds();
+                        if state.phase == RunningPhase::Active && state.accumulator >= tick_seconds
                         {
-                            state.accumulator -= state.tick_seconds;
+                            state.accumulator -= tick_seconds;
                             true
                         } else {
                             false
@@ -707,7 +1663,9 @@ impl SnakeGuiApp {
                     if !should_step {
                         break;
                     }
-                    if self.step_running_tick() || self.screen != ScreenState::Running {
+                    let finished = self.step_running_tick();
+                    self.absorb_run_events();
+                    if finished || self.screen != ScreenState::Running {
                         break;
                     }
                 }
@@ -723,7 +1681,43 @@ impl SnakeGuiApp {
                     self.complete_running_session();
                 }
             }
-            RunningPhase::PointerIdlePause => {}
+            RunningPhase::PointerIdlePause | RunningPhase::Paused => {}
+        }
+    }
+
+    fn absorb_run_events(&mut self) {
+        let Some(state) = self.running.as_mut() else {
+            return;
+        };
+        let board = layout::board(state.run.board);
+        let theme = Theme::for_id(self.engine.profile.theme);
+        let intensity = self.engine.profile.effects_intensity;
+        let pitch_step = synth::pitch_step_for_length(state.run.snake.len());
+        for event in state.run.drain_events() {
+            let cue = match event {
+                RunEvent::FoodEaten { .. } => Some(SoundEvent::Eat),
+                RunEvent::Repositioned { .. } => Some(SoundEvent::Reposition),
+                RunEvent::Died { .. } => Some(SoundEvent::Collision),
+                RunEvent::BoardFilled { .. }
+                | RunEvent::PuzzleSolved { .. }
+                | RunEvent::StageCleared { .. } => Some(SoundEvent::Unlock),
+                RunEvent::OutOfMoves | RunEvent::BorderClosed { .. } => Some(SoundEvent::Collision),
+                RunEvent::GraceStarted { .. }
+                | RunEvent::TimeUp
+                | RunEvent::HazardAppeared { .. } => None,
+            };
+            if let Some(cue) = cue {
+                self.audio
+                    .play_pitched(cue, pitch_step, &self.engine.profile.audio_volume);
+            }
+            if let RunEvent::Repositioned { from, to } = event {
+                state.reposition_trail = Some(RepositionTrail {
+                    from,
+                    to,
+                    remaining: REPOSITION_TRAIL_SECONDS,
+                });
+            }
+            self.effects.trigger(event, intensity, &board, &theme);
         }
     }
 
@@ -736,8 +1730,22 @@ impl SnakeGuiApp {
             return false;
         }
 
+        state.previous_snake = state.run.snake.clone();
         state.run.tick();
+        if engine.check_time_up(&mut state.run) {
+            self.complete_running_session();
+            return true;
+        }
+        state.run.update_food();
+        state.run.update_arena();
         if let Some(next_direction) = state.queued_directions.pop_front() {
+            if next_direction != state.direction {
+                self.audio.play_pitched(
+                    SoundEvent::Turn,
+                    synth::pitch_step_for_length(state.run.snake.len()),
+                    &engine.profile.audio_volume,
+                );
+            }
             state.direction = next_direction;
         }
 
@@ -746,11 +1754,15 @@ impl SnakeGuiApp {
             current_head,
             state.run.board.width,
             state.run.board.height,
-            state.run.effects.has_soft_wrap,
+            state.run.wraps_walls(),
         );
-        let eaten_food_index = state.foods.iter().position(|food| *food == next_head);
-        let ate_food = eaten_food_index.is_some();
-        let collides = snake_collides(next_head, &state.run, ate_food);
+        let eats_food = state
+            .run
+            .foods
+            .iter()
+            .any(|food| food.position == next_head);
+        let tail_stays = eats_food || state.run.pending_growth > 0;
+        let collides = snake_collides(next_head, &state.run, tail_stays);
 
         if collides && state.run.grace_ticks_remaining == 0 {
             state.replay_path = state.run.snake.clone();
@@ -772,32 +1784,29 @@ impl SnakeGuiApp {
         }
 
         state.run.snake.insert(0, next_head);
-        if ate_food {
-            if let Some(index) = eaten_food_index {
-                state.foods.remove(index);
-            }
-            state.run.add_food(1);
-            let refill_count = if state.run.metrics.food_eaten % FOOD_REFILL_EVERY_EATEN == 0 {
-                FOOD_REFILL_COUNT
-            } else {
-                0
-            };
-            let (foods, next_seed) =
-                spawn_food_positions(state.spawn_seed, &state.run, &state.foods, refill_count);
-            state.foods.extend(foods);
-            state.spawn_seed = next_seed;
-        } else {
-            state.run.snake.pop();
+        if let Some(food) = state.run.take_food_at(next_head) {
+            state.run.eat_food(food.kind);
+            state.run.refill_food();
+        }
+        state.run.trim_tail();
+        if engine.check_board_full(&mut state.run)
+            || engine.check_puzzle(&mut state.run)
+            || engine.check_stage(&mut state.run)
+        {
+            self.complete_running_session();
+            return true;
         }
         false
     }
 
     fn complete_running_session(&mut self) {
+        self.absorb_run_events();
         let Some(state) = self.running.take() else {
             return;
         };
+        let unlocked_before = self.engine.profile.unlocked_tool_ids.len();
 
-        let score = policy_for(state.run.mode).score(&state.run.metrics, &state.run.effects);
+        let score = state.run.policy().final_score(&state.run);
         let loadout_summary = state
             .run
             .active_loadout
@@ -805,8 +1814,13 @@ impl SnakeGuiApp {
             .map(|loadout| loadout.summary())
             .unwrap_or_else(|| "none".to_string());
 
-        if let Err(err) = self.engine.finish_run(&state.run) {
+        let run_id = self.engine.finish_run(&state.run).unwrap_or_else(|err| {
             self.message = Some(err);
+            None
+        });
+        if self.engine.profile.unlocked_tool_ids.len() > unlocked_before {
+            self.audio
+                .play(SoundEvent::Unlock, &self.engine.profile.audio_volume);
         }
 
         self.summary = Some(RunSummary {
@@ -815,141 +1829,440 @@ impl SnakeGuiApp {
             survival_ticks: state.run.metrics.survival_ticks,
             food_eaten: state.run.metrics.food_eaten,
             growth_units: state.run.metrics.growth_units,
+            repositions: state.run.metrics.repositions,
             loadout_summary,
+            abandoned: !state.run.is_ranked(),
+            won: state.run.won,
+            perfect: state.run.is_perfect(),
+            timed_out: state.run.timed_out,
+            out_of_moves: state.run.out_of_moves,
+            cleared_stage: state.run.campaign_stage.filter(|_| state.run.won),
+            puzzle_stars: state
+                .run
+                .puzzle
+                .filter(|_| state.run.won)
+                .map(|active| active.puzzle.stars(active.moves_used)),
+            difficulty: state.run.difficulty,
+            run_id,
         });
-        self.leaderboard_mode_cursor = mode_index(state.run.mode);
+        self.leaderboard_mode_cursor = self.mode_index(state.run.mode);
         self.screen = ScreenState::Summary;
     }
 
     pub fn draw(&self) {
-        clear_background(Color::from_rgba(13, 20, 26, 255));
+        let theme = self.theme();
+        clear_background(theme.background);
 
+        let ui = Layout::current();
         match self.screen {
-            ScreenState::MainMenu => self.draw_main_menu(),
-            ScreenState::ModeSelect => self.draw_mode_select(),
-            ScreenState::Loadout => self.draw_loadout(),
-            ScreenState::Running => self.draw_running(),
-            ScreenState::Summary => self.draw_summary(),
-            ScreenState::Leaderboard => self.draw_leaderboard(),
-            ScreenState::Settings => self.draw_settings(),
+            ScreenState::ProfileSelect => self.draw_profile_select(&ui, &theme),
+            ScreenState::NameEntry => self.draw_name_entry(&ui, &theme),
+            ScreenState::MainMenu => self.draw_main_menu(&ui, &theme),
+            ScreenState::ModeSelect => self.draw_mode_select(&ui, &theme),
+            ScreenState::PuzzleSelect => self.draw_puzzle_select(&ui, &theme),
+            ScreenState::CampaignMap => self.draw_campaign_map(&ui, &theme),
+            ScreenState::CustomBuilder => self.draw_custom_builder(&ui, &theme),
+            ScreenState::Loadout => self.draw_loadout(&ui, &theme),
+            ScreenState::Running => self.draw_running(&ui, &theme),
+            ScreenState::Summary => self.draw_summary(&ui, &theme),
+            ScreenState::Leaderboard => self.draw_leaderboard(&ui, &theme),
+            ScreenState::Settings => self.draw_settings(&ui, &theme),
+        }
+        self.draw_message(&ui, &theme);
+    }
+
+    fn draw_profile_select(&self, ui: &Layout, theme: &Theme) {
+        draw_title(ui, theme, "Who's Playing?");
+        ui.text("Profiles", 80.0, 130.0, 40.0, theme.text);
+        let names = self.engine.profile_names();
+        for (i, name) in names.iter().enumerate() {
+            let slot = layout::mode_item(i);
+            let color = if i == self.profile_cursor {
+                theme.accent
+            } else {
+                theme.text_muted
+            };
+            let active = if *name == self.engine.profile.name {
+                "  (active)"
+            } else {
+                ""
+            };
+            ui.text(
+                &format!("{name}{active}"),
+                slot.text.x,
+                slot.text.y,
+                34.0,
+                color,
+            );
         }
-        self.draw_message();
+        let slot = layout::mode_item(names.len());
+        ui.text(
+            "+ New Profile",
+            slot.text.x,
+            slot.text.y,
+            34.0,
+            if self.profile_cursor == names.len() {
+                theme.accent
+            } else {
+                theme.text_hint
+            },
+        );
+        ui.text(
+            "Each profile keeps its own unlocks, settings and progress.",
+            80.0,
+            600.0,
+            24.0,
+            theme.text_hint,
+        );
+        ui.text(
+            "Up/Down: Profile    Enter/dwell: Play as    Esc: Main Menu",
+            80.0,
+            640.0,
+            24.0,
+            theme.text_hint,
+        );
+    }
+
+    fn draw_name_entry(&self, ui: &Layout, theme: &Theme) {
+        draw_title(ui, theme, "New Profile");
+        ui.text("Name", 80.0, 130.0, 40.0, theme.text);
+        ui.text(
+            &format!("{}_", self.name_draft),
+            100.0,
+            220.0,
+            44.0,
+            theme.accent,
+        );
+        ui.text(
+            &format!(
+                "{}/{MAX_PROFILE_NAME_LEN} characters: letters, digits, spaces, - and _",
+                self.name_draft.chars().count()
+            ),
+            100.0,
+            270.0,
+            24.0,
+            theme.text_hint,
+        );
+        ui.text(
+            "Type a name    Enter: Create    Backspace: Delete    Esc: Back",
+            80.0,
+            640.0,
+            24.0,
+            theme.text_hint,
+        );
     }
 
-    fn draw_main_menu(&self) {
-        draw_title("Snake GUI Demo");
-        draw_text("Main Menu", 80.0, 140.0, 40.0, WHITE);
+    fn draw_main_menu(&self, ui: &Layout, theme: &Theme) {
+        draw_title(ui, theme, "Snake GUI Demo");
+        ui.text("Main Menu", 80.0, 140.0, 40.0, theme.text);
+        ui.text(
+            &format!("Player: {}", self.engine.profile.name),
+            560.0,
+            140.0,
+            28.0,
+            theme.text_muted,
+        );
         for (i, item) in MAIN_MENU_ITEMS.iter().enumerate() {
-            let y = 210.0 + i as f32 * 50.0;
+            let slot = layout::main_menu_item(i);
             let color = if i == self.main_menu_cursor {
-                YELLOW
+                theme.highlight
             } else {
-                LIGHTGRAY
+                theme.text_muted
             };
-            draw_text(item, 100.0, y, 34.0, color);
+            ui.text(item, slot.text.x, slot.text.y, 34.0, color);
         }
-        draw_text(
+        ui.text(
             "Arrow/WASD or pointer hover+dwell. Scroll navigates. Top-left dwell = Back.",
             80.0,
             430.0,
             24.0,
-            GRAY,
+            theme.text_hint,
         );
     }
 
-    fn draw_mode_select(&self) {
-        draw_title("Select Mode");
-        draw_text("Mode Select", 80.0, 130.0, 40.0, WHITE);
-        for (i, mode) in MODES.iter().enumerate() {
-            let y = 200.0 + i as f32 * 52.0;
+    fn draw_mode_select(&self, ui: &Layout, theme: &Theme) {
+        draw_title(ui, theme, "Select Mode");
+        ui.text("Mode Select", 80.0, 130.0, 40.0, theme.text);
+        for (i, mode) in self.modes().iter().enumerate() {
+            let slot = layout::mode_item(i);
             let color = if i == self.mode_cursor {
-                Color::from_rgba(95, 242, 153, 255)
+                theme.accent
             } else {
-                LIGHTGRAY
+                theme.text_muted
             };
-            draw_text(mode_label(*mode), 100.0, y, 34.0, color);
+            ui.text(mode.label(), slot.text.x, slot.text.y, 34.0, color);
         }
-        draw_text(
-            "Enter or dwell: Start    Esc or back hotzone: Back",
+        ui.text(
+            &format!(
+                "Difficulty: < {} >",
+                difficulty_label(self.engine.profile.difficulty)
+            ),
+            100.0,
+            570.0,
+            30.0,
+            theme.highlight,
+        );
+        ui.text(
+            "Up/Down: Mode    Left/Right: Difficulty    Enter/dwell: Start    Esc: Back",
             80.0,
-            460.0,
+            615.0,
+            24.0,
+            theme.text_hint,
+        );
+    }
+
+    fn draw_puzzle_select(&self, ui: &Layout, theme: &Theme) {
+        draw_title(ui, theme, "Select Puzzle");
+        ui.text("Puzzles", 80.0, 130.0, 40.0, theme.text);
+        for (i, puzzle) in PUZZLES.iter().enumerate() {
+            let slot = layout::puzzle_item(i);
+            let color = if i == self.puzzle_cursor {
+                theme.accent
+            } else {
+                theme.text_muted
+            };
+            let stars = match self.engine.profile.puzzle_stars(puzzle.id) {
+                0 => "unsolved".to_string(),
+                stars => format!("{stars}/3 stars"),
+            };
+            ui.text(
+                &format!(
+                    "{}. {}  -  {}, {} moves  -  {}",
+                    i + 1,
+                    puzzle.name,
+                    puzzle_goal_label(puzzle.goal),
+                    puzzle.move_budget,
+                    stars
+                ),
+                slot.text.x,
+                slot.text.y,
+                30.0,
+                color,
+            );
+        }
+        ui.text(
+            "Up/Down: Puzzle    Enter or dwell: Start    Esc or back hotzone: Back",
+            80.0,
+            540.0,
+            24.0,
+            theme.text_hint,
+        );
+    }
+
+    fn draw_campaign_map(&self, ui: &Layout, theme: &Theme) {
+        draw_title(ui, theme, "Campaign");
+        ui.text("Campaign Map", 80.0, 130.0, 40.0, theme.text);
+        let profile = &self.engine.profile;
+        for index in 1..STAGES.len() {
+            let from = layout::campaign_node(index - 1).text;
+            let to = layout::campaign_node(index).text;
+            let color = if profile.campaign_stage_unlocked(index) {
+                theme.accent
+            } else {
+                theme.inactive
+            };
+            ui.line(from, to, 4.0, color);
+        }
+        for (index, stage) in STAGES.iter().enumerate() {
+            let node = layout::campaign_node(index);
+            let cleared = profile.campaign_cleared.contains(stage.id);
+            let fill = if cleared {
+                theme.accent
+            } else if profile.campaign_stage_unlocked(index) {
+                theme.panel
+            } else {
+                theme.inactive
+            };
+            ui.circle(node.text, 30.0, fill);
+            if index == self.campaign_cursor {
+                ui.outline(node.hit, 3.0, theme.highlight);
+            }
+            ui.text(
+                &(index + 1).to_string(),
+                node.text.x - 8.0,
+                node.text.y + 10.0,
+                30.0,
+                theme.text,
+            );
+            ui.text(
+                stage.name,
+                node.text.x - 40.0,
+                node.text.y + 62.0,
+                24.0,
+                theme.text_muted,
+            );
+        }
+
+        let stage = &STAGES[self.campaign_cursor];
+        let status = if profile.campaign_cleared.contains(stage.id) {
+            "cleared"
+        } else if profile.campaign_stage_unlocked(self.campaign_cursor) {
+            "open"
+        } else {
+            "locked"
+        };
+        ui.text(
+            &format!(
+                "{}: {} on a {}x{} board ({status})",
+                stage.name,
+                stage_goal_label(stage.goal),
+                stage.board.width,
+                stage.board.height
+            ),
+            80.0,
+            480.0,
+            30.0,
+            theme.highlight,
+        );
+        ui.text(
+            "Left/Right: Stage    Enter or dwell: Play    Esc or back hotzone: Back",
+            80.0,
+            540.0,
+            24.0,
+            theme.text_hint,
+        );
+    }
+
+    fn draw_custom_builder(&self, ui: &Layout, theme: &Theme) {
+        draw_title(ui, theme, "Custom Mode");
+        ui.text("Custom Rules", 80.0, 120.0, 40.0, theme.text);
+        let draft = &self.custom_draft;
+        let profile = &self.engine.profile;
+        let saved = profile.custom_presets.contains(draft);
+        let mut rows = vec![
+            format!(
+                "Preset: < {} >{}",
+                draft.name,
+                if saved { "" } else { "  (unsaved)" }
+            ),
+            format!(
+                "On Collision: {}",
+                match draft.collision {
+                    CollisionOutcome::Die => "die",
+                    CollisionOutcome::Reposition => "reposition",
+                }
+            ),
+            format!(
+                "Walls: {}",
+                if draft.wraps { "wrap around" } else { "solid" }
+            ),
+            format!("Food: {}", FOOD_OPTIONS[draft.food_option()].0),
+            format!("Speed: {}", SPEED_OPTIONS[draft.speed_option()].0),
+            format!("Points Per Food: {}", draft.weights.per_food),
+            format!("Points Per 10 Ticks: {}", draft.weights.per_ten_ticks),
+            format!("Points Per Speed Bonus: {}", draft.weights.per_speed_point),
+        ];
+        for tool in self.tool_ids() {
+            let state = if !profile.unlocked_tool_ids.contains(&tool) {
+                "locked"
+            } else if draft.tools.contains(&tool) {
+                "on"
+            } else {
+                "off"
+            };
+            rows.push(format!("Tool {tool}: {state}"));
+        }
+        rows.push("Save Preset".to_string());
+        rows.push("Play".to_string());
+        for (i, row) in rows.iter().enumerate() {
+            let slot = layout::settings_row(i);
+            let color = if i == self.custom_cursor {
+                theme.highlight
+            } else {
+                theme.text_muted
+            };
+            ui.text(row, slot.text.x, slot.text.y, 28.0, color);
+        }
+        ui.text(
+            "Up/Down: Row    Left/Right: Change    Enter/dwell: Change, Save or Play    Esc: Back",
+            80.0,
+            680.0,
             24.0,
-            GRAY,
+            theme.text_hint,
         );
     }
 
-    fn draw_loadout(&self) {
-        draw_title("Experimental Loadout");
-        draw_text(
+    fn draw_loadout(&self, ui: &Layout, theme: &Theme) {
+        draw_title(ui, theme, "Experimental Loadout");
+        ui.text(
             "Experimental Loadout (3 Slots Required)",
             80.0,
             120.0,
             38.0,
-            WHITE,
+            theme.text,
         );
-        draw_text(
+        ui.text(
             "Slot Focus: Up/Down or pointer hover    Change Tool: Left/Right or scroll",
             80.0,
             165.0,
             24.0,
-            GRAY,
+            theme.text_hint,
         );
 
         let tool_ids = self.tool_ids();
         for slot in 0..3 {
-            let y = 230.0 + slot as f32 * 90.0;
+            let row = layout::loadout_slot(slot);
             let focused = slot == self.loadout_state.slot_cursor;
-            let border = if focused { YELLOW } else { DARKGRAY };
-            draw_rectangle_lines(80.0, y - 42.0, 830.0, 62.0, 2.0, border);
+            let border = if focused {
+                theme.highlight
+            } else {
+                theme.inactive
+            };
+            ui.outline(row.hit, 2.0, border);
             let index = self.loadout_state.selected_tool_indices[slot] % tool_ids.len();
             let tool_id = &tool_ids[index];
             let tool = self.engine.registry.tool(tool_id.as_str()).unwrap();
             let unlocked = self.engine.profile.unlocked_tool_ids.contains(tool_id);
             let category = tool_category_label(tool.category);
             let status = if unlocked { "Unlocked" } else { "Locked" };
-            let color = if unlocked {
-                Color::from_rgba(95, 242, 153, 255)
-            } else {
-                Color::from_rgba(255, 119, 119, 255)
-            };
+            let color = if unlocked { theme.accent } else { theme.danger };
 
-            draw_text(
+            ui.text(
                 &format!("Slot {}: {}", slot + 1, tool_id),
-                100.0,
-                y,
+                row.text.x,
+                row.text.y,
                 30.0,
-                WHITE,
+                theme.text,
+            );
+            ui.text(
+                &format!("{category} | {status}"),
+                row.text.x + 320.0,
+                row.text.y,
+                28.0,
+                color,
             );
-            draw_text(&format!("{category} | {status}"), 420.0, y, 28.0, color);
         }
 
-        draw_text(
+        ui.text(
             "Enter/dwell: Start Experimental    Esc/back hotzone: Back",
             80.0,
             560.0,
             24.0,
-            GRAY,
+            theme.text_hint,
         );
-        draw_text(
+        ui.text(
             "Locked tools cannot be equipped. Unlock via invincible cumulative length: 15/40/80/140...",
             80.0,
             600.0,
             22.0,
-            LIGHTGRAY,
+            theme.text_muted,
         );
     }
 
-    fn draw_running(&self) {
+    fn draw_running(&self, ui: &Layout, theme: &Theme) {
         let Some(state) = self.running.as_ref() else {
             return;
         };
 
-        draw_title("Running");
-        let score = policy_for(state.run.mode).score(&state.run.metrics, &state.run.effects);
-        draw_text(
+        draw_title(ui, theme, "Running");
+        let score = state
+            .run
+            .policy()
+            .score(&state.run.metrics, &state.run.effects);
+        ui.text(
             &format!(
                 "Mode: {}   Score: {}   Ticks: {}   Food: {}   Growth: {}",
-                mode_label(state.run.mode),
+                state.run.mode.label(),
                 score,
                 state.run.metrics.survival_ticks,
                 state.run.metrics.food_eaten,
@@ -958,261 +2271,758 @@ impl SnakeGuiApp {
             40.0,
             52.0,
             30.0,
-            WHITE,
+            theme.text,
         );
-        draw_text(
-            "Arrow/WASD or pointer movement to steer. Esc ends run.",
+        ui.text(
+            "Arrow/WASD or pointer movement to steer. Esc pauses.",
             40.0,
             82.0,
             24.0,
-            GRAY,
+            theme.text_hint,
         );
-        draw_text(
-            "Pointer outside board idle 10ms pauses. Inside board hover keeps steering.",
+        ui.text(
+            "Hover-steer: pointer idle outside board pauses. Configure in Settings.",
             40.0,
             108.0,
             22.0,
-            LIGHTGRAY,
+            theme.text_muted,
         );
 
-        let board_width = state.run.board.width as f32 * CELL_SIZE;
-        let board_height = state.run.board.height as f32 * CELL_SIZE;
-        let origin_x = (screen_width() - board_width) / 2.0;
-        let origin_y = 130.0;
-
-        draw_rectangle_lines(
-            origin_x - 2.0,
-            origin_y - 2.0,
-            board_width + 4.0,
-            board_height + 4.0,
+        let board = layout::board(state.run.board);
+        let frame = board.frame();
+        let playfield = ui.translated(self.effects.shake_offset());
+        playfield.outline(
+            Rect::new(frame.x - 2.0, frame.y - 2.0, frame.w + 4.0, frame.h + 4.0),
             2.0,
-            GRAY,
+            theme.walls,
         );
 
-        for food in &state.foods {
-            draw_cell(origin_x, origin_y, *food, Color::from_rgba(255, 90, 79, 255));
+        for wall in &state.run.walls {
+            draw_cell(&playfield, &board, *wall, theme.walls);
+        }
+        if let Some(arena) = &state.run.arena {
+            for roamer in &arena.roamers {
+                draw_cell(&playfield, &board, roamer.position, theme.danger);
+            }
+        }
+        if let Some(PuzzleGoal::ReachExit(exit)) = state.run.puzzle.map(|active| active.puzzle.goal)
+        {
+            let cell = board.cell_rect(exit);
+            playfield.outline(cell, 3.0, theme.highlight);
+        }
+
+        let tick = state.run.metrics.survival_ticks;
+        for food in &state.run.foods {
+            draw_food(&playfield, theme, &board, food, tick);
+        }
+
+        if let Some(trail) = state.reposition_trail {
+            draw_reposition_trail(&playfield, theme, &board, trail);
         }
 
-        for (i, segment) in state.run.snake.iter().enumerate() {
-            let color = if i == 0 {
-                Color::from_rgba(127, 255, 90, 255)
+        // During grace the snake pulses translucent so it reads as intangible.
+        let grace = state.run.grace_ticks_remaining;
+        let mut snake_theme = *theme;
+        if grace > 0 && (state.blink_clock * GRACE_BLINK_HZ * 2.0) as u32 % 2 == 1 {
+            snake_theme.snake_head.a *= 0.35;
+            snake_theme.snake_body.a *= 0.35;
+        }
+        match self.engine.profile.snake_render {
+            SnakeRenderStyle::Smooth => draw_smooth_snake(&playfield, &snake_theme, &board, state),
+            SnakeRenderStyle::Classic => {
+                for (i, segment) in state.run.snake.iter().enumerate() {
+                    let color = if i == 0 {
+                        snake_theme.snake_head
+                    } else {
+                        snake_theme.snake_body
+                    };
+                    draw_cell(&playfield, &board, *segment, color);
+                }
+            }
+        }
+        if grace > 0 {
+            let x = frame.x + frame.w + 16.0;
+            ui.text("Grace", x, frame.y + 20.0, 24.0, theme.highlight);
+            for pip in 0..grace {
+                ui.circle(
+                    vec2(x + 8.0 + pip as f32 * 20.0, frame.y + 38.0),
+                    6.0,
+                    theme.highlight,
+                );
+            }
+        }
+        if state.run.speed_curve != SpeedCurve::Flat {
+            let x = frame.x - 96.0;
+            ui.text("Speed", x, frame.y + 20.0, 24.0, theme.accent);
+            ui.text(
+                &format!("Lv {}", state.run.speed_level()),
+                x,
+                frame.y + 50.0,
+                28.0,
+                theme.text,
+            );
+        }
+        if let Some(stage) = state.run.campaign_stage.map(|index| &STAGES[index]) {
+            let x = frame.x + frame.w + 16.0;
+            let (done, target) = match stage.goal {
+                StageGoal::EatFood(count) => (state.run.metrics.food_eaten, count),
+                StageGoal::SurviveTicks(ticks) => (state.run.metrics.survival_ticks, ticks),
+                StageGoal::ReachLength(length) => (state.run.snake.len() as u64, length as u64),
+            };
+            ui.text("Goal", x, frame.y + 80.0, 24.0, theme.accent);
+            ui.text(
+                &format!("{}/{}", done.min(target), target),
+                x,
+                frame.y + 110.0,
+                28.0,
+                theme.text,
+            );
+        }
+        if let Some(active) = state.run.puzzle {
+            let x = frame.x + frame.w + 16.0;
+            ui.text("Moves", x, frame.y + 80.0, 24.0, theme.accent);
+            ui.text(
+                &format!("{} left", active.moves_remaining()),
+                x,
+                frame.y + 110.0,
+                28.0,
+                theme.text,
+            );
+        }
+        if let Some(arena) = &state.run.arena {
+            let x = frame.x + frame.w + 16.0;
+            let next = arena.ticks_until_next(state.run.metrics.survival_ticks);
+            ui.text(
+                &format!("Hazards {}", arena.level),
+                x,
+                frame.y + 80.0,
+                24.0,
+                theme.accent,
+            );
+            ui.text(
+                &format!("next in {next}"),
+                x,
+                frame.y + 110.0,
+                24.0,
+                theme.text,
+            );
+        }
+        if let Some(seconds) = state.time_remaining_seconds() {
+            let x = frame.x + frame.w + 16.0;
+            let color = if seconds < TIME_WARNING_SECONDS {
+                theme.danger
             } else {
-                Color::from_rgba(89, 196, 64, 255)
+                theme.text
             };
-            draw_cell(origin_x, origin_y, *segment, color);
+            ui.text("Time", x, frame.y + 80.0, 24.0, theme.accent);
+            ui.text(&format!("{seconds:.1}s"), x, frame.y + 110.0, 28.0, color);
+        }
+        self.effects.draw(ui);
+
+        if self.engine.profile.show_dpad {
+            draw_dpad(ui, theme);
         }
 
         match state.phase {
             RunningPhase::PointerIdlePause => {
-                draw_text(
+                ui.text(
                     "Paused: pointer idle detected. Move pointer >2px or press arrow to resume.",
                     40.0,
                     660.0,
                     26.0,
-                    YELLOW,
+                    theme.highlight,
                 );
             }
             RunningPhase::Replay => {
-                draw_text(
+                ui.text(
                     "Replay on death active (mortal mode only)",
                     40.0,
                     660.0,
                     28.0,
-                    YELLOW,
+                    theme.highlight,
                 );
                 for segment in &state.replay_path {
-                    draw_cell(
-                        origin_x,
-                        origin_y,
-                        *segment,
-                        Color::from_rgba(255, 255, 255, 38),
-                    );
+                    draw_cell(ui, &board, *segment, theme.ghost);
                 }
             }
+            RunningPhase::Paused => self.draw_pause_overlay(ui, theme, state),
             RunningPhase::Active => {}
         }
     }
 
-    fn draw_summary(&self) {
-        draw_title("Run Summary");
-        draw_text("Summary", 80.0, 120.0, 40.0, WHITE);
+    fn draw_pause_overlay(&self, ui: &Layout, theme: &Theme, state: &RunningState) {
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), theme.overlay);
+        let panel = layout::pause_panel();
+        ui.fill(panel, theme.panel);
+        ui.outline(panel, 2.0, theme.text_hint);
+        ui.text("Paused", panel.x + 30.0, panel.y + 50.0, 40.0, theme.text);
+        for (i, item) in PAUSE_MENU_ITEMS.iter().enumerate() {
+            let slot = layout::pause_item(i);
+            let color = if i == state.pause_cursor {
+                theme.highlight
+            } else {
+                theme.text_muted
+            };
+            ui.text(item, slot.text.x, slot.text.y, 32.0, color);
+        }
+        ui.text(
+            "Enter/dwell: Select    Esc: Resume",
+            panel.x + 10.0,
+            panel.bottom() - 15.0,
+            22.0,
+            theme.text_hint,
+        );
+    }
+
+    fn draw_summary(&self, ui: &Layout, theme: &Theme) {
+        draw_title(ui, theme, "Run Summary");
+        ui.text("Summary", 80.0, 120.0, 40.0, theme.text);
         if let Some(summary) = &self.summary {
-            draw_text(
-                &format!("Mode: {}", mode_label(summary.mode)),
-                100.0,
-                200.0,
-                34.0,
-                LIGHTGRAY,
-            );
-            draw_text(
-                &format!("Score: {}", summary.score),
-                100.0,
-                245.0,
-                34.0,
-                LIGHTGRAY,
-            );
-            draw_text(
-                &format!("Survival Ticks: {}", summary.survival_ticks),
-                100.0,
-                290.0,
-                34.0,
-                LIGHTGRAY,
-            );
-            draw_text(
-                &format!("Food Eaten: {}", summary.food_eaten),
-                100.0,
-                335.0,
-                34.0,
-                LIGHTGRAY,
-            );
-            draw_text(
-                &format!("Growth Units: {}", summary.growth_units),
-                100.0,
-                380.0,
-                34.0,
-                LIGHTGRAY,
-            );
-            draw_text(
-                &format!("Loadout: {}", summary.loadout_summary),
-                100.0,
-                425.0,
-                34.0,
-                LIGHTGRAY,
-            );
+            let lines = [
+                format!(
+                    "Mode: {} ({})",
+                    summary.mode.label(),
+                    difficulty_label(summary.difficulty)
+                ),
+                format!("Score: {}", summary.score),
+                format!("Survival Ticks: {}", summary.survival_ticks),
+                format!("Food Eaten: {}", summary.food_eaten),
+                format!("Growth Units: {}", summary.growth_units),
+                format!("Repositions: {}", summary.repositions),
+                format!("Loadout: {}", summary.loadout_summary),
+            ];
+            for (i, line) in lines.iter().enumerate() {
+                ui.text(line, 100.0, 200.0 + i as f32 * 45.0, 34.0, theme.text_muted);
+            }
+            if summary.abandoned {
+                ui.text(
+                    "Run abandoned: not submitted to the leaderboard.",
+                    100.0,
+                    520.0,
+                    28.0,
+                    theme.highlight,
+                );
+            } else if let Some(index) = summary.cleared_stage {
+                let outcome = match STAGES.get(index + 1) {
+                    Some(next) => format!("Stage cleared! {} is now unlocked.", next.name),
+                    None => "Campaign complete!".to_string(),
+                };
+                ui.text(&outcome, 100.0, 520.0, 28.0, theme.accent);
+            } else if let Some(stars) = summary.puzzle_stars {
+                ui.text(
+                    &format!("Puzzle solved: {stars}/3 stars!"),
+                    100.0,
+                    520.0,
+                    28.0,
+                    theme.accent,
+                );
+            } else if summary.out_of_moves {
+                ui.text(
+                    "Out of moves: try a shorter route.",
+                    100.0,
+                    520.0,
+                    28.0,
+                    theme.highlight,
+                );
+            } else if summary.won {
+                let outcome = if summary.perfect {
+                    "Board filled: perfect game!"
+                } else {
+                    "Board filled: run won."
+                };
+                ui.text(outcome, 100.0, 520.0, 28.0, theme.accent);
+            } else if summary.timed_out {
+                ui.text(
+                    "Time up: food eaten before the clock ran out counts.",
+                    100.0,
+                    520.0,
+                    28.0,
+                    theme.accent,
+                );
+            }
         }
 
-        draw_text(
+        ui.text(
             "Enter/dwell: Main Menu    Right: Leaderboards    Back hotzone: Main Menu",
             80.0,
             560.0,
             24.0,
-            GRAY,
+            theme.text_hint,
         );
     }
 
-    fn draw_leaderboard(&self) {
-        draw_title("Leaderboards");
-        let mode = MODES[self.leaderboard_mode_cursor];
-        draw_text(
-            &format!("Leaderboard: {}", mode_label(mode)),
+    fn draw_leaderboard(&self, ui: &Layout, theme: &Theme) {
+        draw_title(ui, theme, "Leaderboards");
+        let mode = self.modes()[self.leaderboard_mode_cursor];
+        let control_color = |control: usize| {
+            if control == self.leaderboard_focus {
+                theme.highlight
+            } else {
+                theme.text_hint
+            }
+        };
+        ui.text(
+            &format!("Leaderboard: < {} >", mode.label()),
             80.0,
             120.0,
             40.0,
-            WHITE,
-        );
-        draw_text(
-            "Rows include mode, score, survival, and loadout metadata",
-            80.0,
-            160.0,
-            24.0,
-            GRAY,
+            if self.leaderboard_focus == 0 {
+                theme.highlight
+            } else {
+                theme.text
+            },
         );
-
-        for (row_index, row) in self
-            .engine
-            .leaderboards
-            .rows(mode)
-            .iter()
-            .take(10)
-            .enumerate()
-        {
-            let y = 230.0 + row_index as f32 * 42.0;
-            draw_text(
-                &format!(
-                    "{:02}. mode={} score={} ticks={} loadout={}",
-                    row_index + 1,
-                    mode_label(row.mode),
+        let filter = &self.leaderboard_filter;
+        let view = if mode != GameMode::EXPERIMENTAL {
+            "all runs (best per loadout: Experimental only)"
+        } else if self.leaderboard_best_per_loadout {
+            "best per loadout"
+        } else {
+            "all runs"
+        };
+        let controls = [
+            (
+                1,
+                80.0,
+                160.0,
+                format!(
+                    "Difficulty: {}",
+                    filter.difficulty.map_or("all", difficulty_label)
+                ),
+            ),
+            (
+                3,
+                330.0,
+                160.0,
+                format!(
+                    "Board: {}",
+                    filter
+                        .board
+                        .map_or("all".to_string(), |b| format!("{}x{}", b.width, b.height))
+                ),
+            ),
+            (
+                4,
+                580.0,
+                160.0,
+                format!(
+                    "Date: {}",
+                    LEADERBOARD_DATE_RANGES[self.leaderboard_date_range].0
+                ),
+            ),
+            (
+                2,
+                80.0,
+                190.0,
+                format!("Loadout: {}", filter.loadout.as_deref().unwrap_or("all")),
+            ),
+            (5, 580.0, 190.0, format!("View: {view}")),
+        ];
+        for (control, x, y, label) in controls {
+            ui.text(&label, x, y, 24.0, control_color(control));
+        }
+        ui.text(
+            &format!("Ranked by: {}", ranking_label(&mode.policy().ranking())),
+            80.0,
+            220.0,
+            24.0,
+            theme.text_hint,
+        );
+
+        let rows = self.leaderboard_rows();
+        let page = self.leaderboard_page.min(self.leaderboard_page_count() - 1);
+        let first = page * LEADERBOARD_PAGE_ROWS;
+        for (offset, row) in rows
+            .iter()
+            .skip(first)
+            .take(LEADERBOARD_PAGE_ROWS)
+            .enumerate()
+        {
+            let y = 256.0 + offset as f32 * 36.0;
+            let just_played = self.leaderboard_highlight == Some(row.run_id);
+            ui.text(
+                &format!(
+                    "{:02}. {} mode={} ({}) score={} ticks={} board={}x{} loadout={}{}{}{}",
+                    first + offset + 1,
+                    row.player,
+                    row.mode.label(),
+                    difficulty_label(row.difficulty),
                     row.score,
                     row.survival_ticks,
-                    row.loadout_summary
+                    row.board.width,
+                    row.board.height,
+                    row.loadout_summary,
+                    row.preset
+                        .as_deref()
+                        .map(|name| format!(" preset={name}"))
+                        .unwrap_or_default(),
+                    if row.perfect { "  PERFECT" } else { "" },
+                    if just_played { "  < YOUR RUN" } else { "" }
                 ),
                 100.0,
                 y,
-                28.0,
-                LIGHTGRAY,
+                26.0,
+                if just_played {
+                    theme.accent
+                } else {
+                    theme.text_muted
+                },
             );
         }
-        if self.engine.leaderboards.rows(mode).is_empty() {
-            draw_text("No runs yet for this mode.", 100.0, 230.0, 30.0, LIGHTGRAY);
+        if rows.is_empty() {
+            ui.text(
+                "No runs match these filters.",
+                100.0,
+                256.0,
+                30.0,
+                theme.text_muted,
+            );
         }
+        ui.text(
+            &format!(
+                "Page {}/{}    {} rows",
+                page + 1,
+                self.leaderboard_page_count(),
+                rows.len()
+            ),
+            80.0,
+            620.0,
+            24.0,
+            control_color(7),
+        );
+        ui.text(
+            &format!(
+                "Files: < {} >  Enter runs it",
+                LEADERBOARD_FILE_ACTIONS[self.leaderboard_file_action].0
+            ),
+            460.0,
+            620.0,
+            24.0,
+            control_color(6),
+        );
 
-        draw_text(
-            "Left/Right or scroll: Change Mode    Enter/dwell/Esc: Main Menu",
+        ui.text(
+            "Up/Down or scroll: Pick Control    Left/Right: Change It    Enter/dwell/Esc: Main Menu",
             80.0,
-            640.0,
+            660.0,
             24.0,
-            GRAY,
+            theme.text_hint,
         );
     }
 
-    fn draw_settings(&self) {
-        draw_title("Settings");
-        draw_text("Settings", 80.0, 120.0, 40.0, WHITE);
-        let status = if self.engine.profile.replay_on_death {
-            "Enabled"
-        } else {
-            "Disabled"
-        };
-        draw_text(
-            &format!("Replay On Death (Mortal Modes): {}", status),
-            100.0,
-            220.0,
-            34.0,
-            LIGHTGRAY,
-        );
-        draw_text(
-            "Left/Right/Enter/dwell: Toggle    Esc/back hotzone: Back",
+    fn draw_settings(&self, ui: &Layout, theme: &Theme) {
+        draw_title(ui, theme, "Settings");
+        ui.text("Settings", 80.0, 120.0, 40.0, theme.text);
+        let rows = self.settings_rows();
+        let visible = rows
+            .iter()
+            .enumerate()
+            .skip(self.settings_scroll)
+            .take(SETTINGS_VISIBLE_ROWS);
+        for (slot_index, (i, row)) in visible.enumerate() {
+            let slot = layout::settings_row(slot_index);
+            let color = if i == self.settings_cursor {
+                theme.highlight
+            } else {
+                theme.text_muted
+            };
+            ui.text(row, slot.text.x, slot.text.y, 28.0, color);
+        }
+        if self.settings_scroll > 0 {
+            ui.text("^ more", 820.0, 150.0, 22.0, theme.text_hint);
+        }
+        if self.settings_scroll + SETTINGS_VISIBLE_ROWS < SETTINGS_ROW_COUNT {
+            ui.text("v more", 820.0, 570.0, 22.0, theme.text_hint);
+        }
+        ui.text(
+            "Up/Down: Row    Left/Right/Enter/dwell/tap: Change    Esc/back hotzone: Back",
             80.0,
-            300.0,
+            600.0,
             24.0,
-            GRAY,
+            theme.text_hint,
         );
-        draw_text(
+        ui.text(
             "Invincible mode always bypasses replay and continues with reposition.",
             80.0,
-            360.0,
+            628.0,
+            24.0,
+            theme.text_muted,
+        );
+        ui.text(
+            "Pointer control is stored per mode; idle pause only applies to hover-steer.",
+            80.0,
+            656.0,
             24.0,
-            LIGHTGRAY,
+            theme.text_muted,
         );
     }
 
-    fn draw_message(&self) {
+    fn settings_rows(&self) -> [String; SETTINGS_ROW_COUNT] {
+        let profile = &self.engine.profile;
+        let tuning = profile.pointer_tuning;
+        let mode = self.modes()[self.settings_mode_cursor];
+        let replay = if profile.replay_on_death {
+            "Enabled"
+        } else {
+            "Disabled"
+        };
+        [
+            format!("Replay On Death (Mortal Modes): {replay}"),
+            format!("Pointer Settings For: {}", mode.label()),
+            format!(
+                "Pointer Control: {}",
+                pointer_control_label(profile.pointer_control(mode))
+            ),
+            format!("Idle Pause Delay: {} ms", tuning.idle_pause_ms),
+            format!("Pointer Move Threshold: {} px", tuning.displacement_px),
+            format!("Dwell Confirm: {} ms", tuning.dwell_ms),
+            format!("Idle Resume Grace: {} ms", tuning.idle_grace_ms),
+            format!("Swipe Min Distance: {} px", tuning.swipe_min_px),
+            format!("Swipe Min Speed: {} px/s", tuning.swipe_min_px_per_second),
+            format!(
+                "On-screen D-pad: {}",
+                if profile.show_dpad { "Shown" } else { "Hidden" }
+            ),
+            format!(
+                "Snake Rendering: {}",
+                match profile.snake_render {
+                    SnakeRenderStyle::Smooth => "smooth",
+                    SnakeRenderStyle::Classic => "classic",
+                }
+            ),
+            format!(
+                "Effects: {}",
+                match profile.effects_intensity {
+                    EffectsIntensity::Off => "off",
+                    EffectsIntensity::Low => "low (no shake or flashes)",
+                    EffectsIntensity::Full => "full",
+                }
+            ),
+            format!("Master Volume: {}%", profile.audio_volume.master_percent),
+            format!("Effects Volume: {}%", profile.audio_volume.sfx_percent),
+            format!("Music Volume: {}%", profile.audio_volume.music_percent),
+            format!("Theme: {}", theme_label(profile.theme)),
+        ]
+    }
+
+    fn draw_message(&self, ui: &Layout, theme: &Theme) {
         if let Some(message) = self.message.as_ref() {
-            draw_rectangle(
-                40.0,
-                screen_height() - 80.0,
-                screen_width() - 80.0,
-                42.0,
-                Color::from_rgba(35, 12, 12, 230),
-            );
-            draw_text(
+            let bar = layout::message_bar();
+            ui.fill(bar, theme.message_background);
+            ui.text(
                 &format!("Message: {message}"),
-                52.0,
-                screen_height() - 50.0,
+                bar.x + 12.0,
+                bar.y + 30.0,
                 24.0,
-                Color::from_rgba(255, 135, 135, 255),
+                theme.message_text,
+            );
+        }
+    }
+}
+
+fn draw_title(ui: &Layout, theme: &Theme, title: &str) {
+    ui.text(title, 32.0, 56.0, 46.0, theme.accent);
+}
+
+fn draw_dpad(ui: &Layout, theme: &Theme) {
+    for direction in DIRECTIONS {
+        let rect = layout::dpad_button(direction);
+        ui.fill(rect, theme.panel);
+        ui.outline(rect, 2.0, theme.text_hint);
+        let glyph = match direction {
+            Direction::Up => "^",
+            Direction::Down => "v",
+            Direction::Left => "<",
+            Direction::Right => ">",
+        };
+        ui.text(glyph, rect.x + 24.0, rect.y + 42.0, 36.0, theme.text);
+    }
+}
+
+/// Each kind gets its own shape so foods stay distinguishable without color.
+fn draw_food(ui: &Layout, theme: &Theme, board: &BoardLayout, food: &Food, tick: u64) {
+    let color = theme.food_color(food.kind);
+    let cell = board.cell_rect(food.position);
+    let center = cell.center();
+    let radius = board.cell * 0.4;
+    match food.kind {
+        FoodKind::Normal => draw_cell(ui, board, food.position, color),
+        FoodKind::Golden => {
+            ui.circle(center, radius, color);
+            ui.circle(center, radius * 0.45, theme.background);
+        }
+        FoodKind::Shrinking => {
+            let inset = board.cell * 0.25;
+            ui.outline(
+                Rect::new(
+                    cell.x + inset,
+                    cell.y + inset,
+                    cell.w - 2.0 * inset,
+                    cell.h - 2.0 * inset,
+                ),
+                3.0,
+                color,
+            );
+        }
+        FoodKind::SpeedUp => {
+            for offset in [-0.2, 0.15] {
+                let tip = center + vec2(offset * board.cell + radius * 0.5, 0.0);
+                let back = tip - vec2(radius * 0.6, 0.0);
+                ui.line(back - vec2(0.0, radius * 0.6), tip, 3.0, color);
+                ui.line(back + vec2(0.0, radius * 0.6), tip, 3.0, color);
+            }
+        }
+        FoodKind::Bonus => {
+            ui.circle(center, radius * 0.6, color);
+            let remaining = food.remaining_fraction(tick);
+            ui.fill(
+                Rect::new(
+                    cell.x + 2.0,
+                    cell.y + cell.h - 4.0,
+                    (cell.w - 4.0) * remaining,
+                    3.0,
+                ),
+                color,
             );
         }
     }
 }
 
-fn draw_title(title: &str) {
-    draw_text(title, 32.0, 56.0, 46.0, Color::from_rgba(95, 242, 153, 255));
+fn draw_cell(ui: &Layout, board: &BoardLayout, point: Point, color: Color) {
+    let cell = board.cell_rect(point);
+    ui.fill(
+        Rect::new(cell.x + 1.0, cell.y + 1.0, cell.w - 2.0, cell.h - 2.0),
+        color,
+    );
 }
 
-fn draw_cell(origin_x: f32, origin_y: f32, point: Point, color: Color) {
-    let x = origin_x + point.x as f32 * CELL_SIZE;
-    let y = origin_y + point.y as f32 * CELL_SIZE;
-    draw_rectangle(x + 1.0, y + 1.0, CELL_SIZE - 2.0, CELL_SIZE - 2.0, color);
+/// Draws the snake as a continuous rounded body gliding between cells, with
+/// eyes on the head facing the current heading.
+fn draw_smooth_snake(ui: &Layout, theme: &Theme, board: &BoardLayout, state: &RunningState) {
+    let alpha = (state.accumulator / state.effective_tick_seconds()).clamp(0.0, 1.0);
+    let centers: Vec<Vec2> = interpolate_segments(&state.previous_snake, &state.run.snake, alpha)
+        .into_iter()
+        .map(|cell| board.cell_origin(cell.x + 0.5, cell.y + 0.5))
+        .collect();
+    let radius = board.cell * 0.42;
+
+    for (i, pair) in centers.windows(2).enumerate() {
+        let (a, b) = (state.run.snake[i], state.run.snake[i + 1]);
+        if (a.x - b.x).abs() + (a.y - b.y).abs() == 1 {
+            ui.line(pair[0], pair[1], radius * 2.0, theme.snake_body);
+        }
+    }
+    for center in centers.iter().skip(1).rev() {
+        ui.circle(*center, radius, theme.snake_body);
+    }
+
+    let Some(head) = centers.first().copied() else {
+        return;
+    };
+    ui.circle(head, radius * 1.08, theme.snake_head);
+    let (dx, dy) = match state.direction {
+        Direction::Up => (0.0, -1.0),
+        Direction::Down => (0.0, 1.0),
+        Direction::Left => (-1.0, 0.0),
+        Direction::Right => (1.0, 0.0),
+    };
+    let forward = vec2(dx, dy) * radius * 0.35;
+    let side = vec2(-dy, dx) * radius * 0.45;
+    for eye in [head + forward + side, head + forward - side] {
+        ui.circle(eye, radius * 0.18, theme.background);
+    }
 }
 
-fn mode_label(mode: GameMode) -> &'static str {
-    match mode {
-        GameMode::Practice => "practice",
-        GameMode::Challenge => "challenge",
-        GameMode::Experimental => "experimental",
-        GameMode::Invincible => "invincible",
+fn draw_reposition_trail(ui: &Layout, theme: &Theme, board: &BoardLayout, trail: RepositionTrail) {
+    let center = |point: Point| board.cell_origin(point.x as f32 + 0.5, point.y as f32 + 0.5);
+    let (from, to) = (center(trail.from), center(trail.to));
+    let mut color = theme.accent;
+    color.a *= (trail.remaining / REPOSITION_TRAIL_SECONDS).clamp(0.0, 1.0);
+
+    let dashes = ((to - from).length() / (board.cell * 0.5)).ceil().max(1.0) as usize;
+    for dash in (0..dashes).step_by(2) {
+        let start = from.lerp(to, dash as f32 / dashes as f32);
+        let end = from.lerp(to, (dash + 1) as f32 / dashes as f32);
+        ui.line(start, end, 3.0, color);
     }
+    ui.circle(from, board.cell * 0.3, color);
+}
+
+/// Blends each segment from its previous-tick cell toward its current cell.
+/// Segments that jumped more than one cell (wrap or reposition) snap instead of
+/// sliding across the board, and a freshly grown tail starts at the old tail.
+fn interpolate_segments(previous: &[Point], current: &[Point], alpha: f32) -> Vec<Vec2> {
+    current
+        .iter()
+        .enumerate()
+        .map(|(i, to)| {
+            let to_cell = vec2(to.x as f32, to.y as f32);
+            let Some(from) = previous.get(i).or(previous.last()) else {
+                return to_cell;
+            };
+            if (from.x - to.x).abs() + (from.y - to.y).abs() > 1 {
+                return to_cell;
+            }
+            vec2(from.x as f32, from.y as f32).lerp(to_cell, alpha)
+        })
+        .collect()
+}
+
+fn pointer_control_label(control: PointerControl) -> &'static str {
+    match control {
+        PointerControl::Off => "off",
+        PointerControl::HoverSteer => "hover-steer",
+        PointerControl::DragSteer => "drag-steer",
+        PointerControl::ClickToTurn => "click-to-turn",
+        PointerControl::Swipe => "swipe",
+    }
+}
+
+fn stage_goal_label(goal: StageGoal) -> String {
+    match goal {
+        StageGoal::EatFood(count) => format!("eat {count} food"),
+        StageGoal::SurviveTicks(ticks) => format!("survive {ticks} ticks"),
+        StageGoal::ReachLength(length) => format!("grow to {length}"),
+    }
+}
+
+fn puzzle_goal_label(goal: PuzzleGoal) -> String {
+    match goal {
+        PuzzleGoal::EatAll => "eat all food".to_string(),
+        PuzzleGoal::ReachExit(_) => "reach the exit".to_string(),
+        PuzzleGoal::ReachLength(length) => format!("grow to {length}"),
+    }
+}
+
+fn difficulty_label(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Easy => "easy",
+        Difficulty::Normal => "normal",
+        Difficulty::Hard => "hard",
+        Difficulty::Insane => "insane",
+    }
+}
+
+fn rank_key_label(key: RankKey) -> &'static str {
+    match (key.field, key.order) {
+        (RankField::Score, RankOrder::Highest) => "highest score",
+        (RankField::Score, RankOrder::Lowest) => "lowest score",
+        (RankField::Survival, RankOrder::Highest) => "longest survival",
+        (RankField::Survival, RankOrder::Lowest) => "quickest run",
+        (RankField::Food, RankOrder::Highest) => "most food",
+        (RankField::Food, RankOrder::Lowest) => "least food",
+        (RankField::Growth, RankOrder::Highest) => "most growth",
+        (RankField::Growth, RankOrder::Lowest) => "least growth",
+        (RankField::Date, RankOrder::Highest) => "newest",
+        (RankField::Date, RankOrder::Lowest) => "oldest",
+    }
+}
+
+/// The ranking's keys in order, ending with the timestamp tie-break.
+fn ranking_label(ranking: &Ranking) -> String {
+    let mut keys: Vec<&str> = ranking.keys().map(rank_key_label).collect();
+    keys.push("earliest run");
+    keys.join(", then ")
 }
 
 fn tool_category_label(category: ToolCategory) -> &'static str {
@@ -1223,11 +3033,39 @@ fn tool_category_label(category: ToolCategory) -> &'static str {
     }
 }
 
-fn mode_index(mode: GameMode) -> usize {
-    MODES
-        .iter()
-        .position(|candidate| *candidate == mode)
-        .unwrap_or(0)
+/// A fresh draft named after the first free "Custom N".
+fn new_custom_draft(profile: &Profile) -> CustomRules {
+    let name = (1..)
+        .map(|n| format!("Custom {n}"))
+        .find(|name| {
+            profile
+                .custom_presets
+                .iter()
+                .all(|preset| preset.name != *name)
+        })
+        .unwrap_or_default();
+    CustomRules {
+        name,
+        ..CustomRules::default()
+    }
+}
+
+fn step_setting(current: u32, delta: i32, step: u32, min: u32, max: u32) -> u32 {
+    let next = if delta < 0 {
+        current.saturating_sub(step)
+    } else {
+        current.saturating_add(step)
+    };
+    next.clamp(min, max)
+}
+
+/// Steps through `None` followed by each of `choices`.
+fn cycle_choice<T: Clone + PartialEq>(choices: &[T], current: Option<T>, delta: i32) -> Option<T> {
+    let index = current
+        .and_then(|wanted| choices.iter().position(|choice| *choice == wanted))
+        .map_or(0, |position| position + 1);
+    let next = cycle_index(index, delta, choices.len() + 1);
+    next.checked_sub(1).map(|i| choices[i].clone())
 }
 
 fn cycle_index(current: usize, delta: i32, len: usize) -> usize {
@@ -1238,8 +3076,8 @@ fn cycle_index(current: usize, delta: i32, len: usize) -> usize {
     (((current as i32 + delta) % len_i + len_i) % len_i) as usize
 }
 
-fn direction_from_delta(delta: Vec2) -> Option<Direction> {
-    if delta.length() <= POINTER_DISPLACEMENT_THRESHOLD {
+fn direction_from_delta(delta: Vec2, threshold: f32) -> Option<Direction> {
+    if delta.length() <= threshold {
         return None;
     }
     if delta.x.abs() >= delta.y.abs() {
@@ -1278,88 +3116,57 @@ fn direction_toward_pointer(state: &RunningState, pointer_position: Vec2) -> Opt
 }
 
 fn pointer_board_cell(state: &RunningState, pointer_position: Vec2) -> Option<Point> {
-    let board_width = state.run.board.width as f32 * CELL_SIZE;
-    let board_height = state.run.board.height as f32 * CELL_SIZE;
-    let origin_x = (ui_screen_width() - board_width) / 2.0;
-    let origin_y = 130.0;
-
-    if pointer_position.x < origin_x
-        || pointer_position.x >= origin_x + board_width
-        || pointer_position.y < origin_y
-        || pointer_position.y >= origin_y + board_height
-    {
-        return None;
-    }
-
-    let x = ((pointer_position.x - origin_x) / CELL_SIZE).floor() as i32;
-    let y = ((pointer_position.y - origin_y) / CELL_SIZE).floor() as i32;
-    Some(Point { x, y })
+    layout::board(state.run.board).cell_at(pointer_position)
 }
 
-#[cfg(test)]
-fn ui_screen_width() -> f32 {
-    WINDOW_WIDTH as f32
+fn main_menu_item_at(pointer_position: Vec2) -> Option<usize> {
+    (0..MAIN_MENU_ITEMS.len()).find(|index| {
+        layout::main_menu_item(*index)
+            .hit
+            .contains(pointer_position)
+    })
 }
 
-#[cfg(not(test))]
-fn ui_screen_width() -> f32 {
-    screen_width()
+fn campaign_node_at(pointer_position: Vec2) -> Option<usize> {
+    (0..STAGES.len()).find(|index| layout::campaign_node(*index).hit.contains(pointer_position))
 }
 
-fn main_menu_item_at(pointer_position: Vec2) -> Option<usize> {
-    if pointer_position.x < 80.0 || pointer_position.x > 480.0 {
-        return None;
-    }
-    MAIN_MENU_ITEMS.iter().enumerate().find_map(|(index, _)| {
-        let y = 210.0 + index as f32 * 50.0;
-        let top = y - 36.0;
-        let bottom = y + 12.0;
-        (pointer_position.y >= top && pointer_position.y <= bottom).then_some(index)
-    })
+fn puzzle_item_at(pointer_position: Vec2) -> Option<usize> {
+    (0..PUZZLES.len()).find(|index| layout::puzzle_item(*index).hit.contains(pointer_position))
 }
 
-fn mode_item_at(pointer_position: Vec2) -> Option<usize> {
-    if pointer_position.x < 80.0 || pointer_position.x > 520.0 {
-        return None;
-    }
-    MODES.iter().enumerate().find_map(|(index, _)| {
-        let y = 200.0 + index as f32 * 52.0;
-        let top = y - 36.0;
-        let bottom = y + 12.0;
-        (pointer_position.y >= top && pointer_position.y <= bottom).then_some(index)
-    })
+fn mode_item_at(pointer_position: Vec2, count: usize) -> Option<usize> {
+    (0..count).find(|index| layout::mode_item(*index).hit.contains(pointer_position))
 }
 
 fn loadout_slot_at(pointer_position: Vec2) -> Option<usize> {
-    if pointer_position.x < 80.0 || pointer_position.x > 910.0 {
-        return None;
-    }
-    (0..3).find(|slot| {
-        let y = 230.0 + *slot as f32 * 90.0;
-        pointer_position.y >= (y - 42.0) && pointer_position.y <= (y + 20.0)
-    })
+    (0..3).find(|slot| layout::loadout_slot(*slot).hit.contains(pointer_position))
 }
 
-fn settings_toggle_hit(pointer_position: Vec2) -> bool {
-    pointer_position.x >= 90.0
-        && pointer_position.x <= 910.0
-        && pointer_position.y >= 185.0
-        && pointer_position.y <= 245.0
+fn pause_item_at(pointer_position: Vec2) -> Option<usize> {
+    (0..PAUSE_MENU_ITEMS.len())
+        .find(|index| layout::pause_item(*index).hit.contains(pointer_position))
 }
 
-fn pointer_in_back_hotzone(pointer_position: Vec2) -> bool {
-    pointer_position.x >= 16.0
-        && pointer_position.x <= 136.0
-        && pointer_position.y >= 18.0
-        && pointer_position.y <= 72.0
+fn settings_row_at(pointer_position: Vec2, scroll: usize) -> Option<usize> {
+    (0..SETTINGS_VISIBLE_ROWS)
+        .find(|slot| layout::settings_row(*slot).hit.contains(pointer_position))
+        .map(|slot| slot + scroll)
+        .filter(|row| *row < SETTINGS_ROW_COUNT)
+}
+
+fn dpad_button_at(pointer_position: Vec2) -> Option<Direction> {
+    DIRECTIONS
+        .into_iter()
+        .find(|direction| layout::dpad_button(*direction).contains(pointer_position))
 }
 
-fn is_within_board(point: Point, board_width: i32, board_height: i32) -> bool {
-    point.x >= 0 && point.y >= 0 && point.x < board_width && point.y < board_height
+fn pointer_in_back_hotzone(pointer_position: Vec2) -> bool {
+    layout::back_hotzone().contains(pointer_position)
 }
 
 fn snake_collides(next_head: Point, run: &GameRun, ate_food: bool) -> bool {
-    if !is_within_board(next_head, run.board.width, run.board.height) {
+    if run.hits_wall(next_head) {
         return true;
     }
     let body_limit = if ate_food {
@@ -1373,68 +3180,6 @@ fn snake_collides(next_head: Point, run: &GameRun, ate_food: bool) -> bool {
         .any(|segment| *segment == next_head)
 }
 
-fn spawn_food_positions(
-    mut seed: u64,
-    run: &GameRun,
-    existing_foods: &[Point],
-    count: usize,
-) -> (Vec<Point>, u64) {
-    let mut spawned = Vec::with_capacity(count);
-    for _ in 0..count {
-        let occupied: Vec<Point> = existing_foods
-            .iter()
-            .copied()
-            .chain(spawned.iter().copied())
-            .collect();
-        let (food, next_seed) = next_food_position(seed, run, &occupied);
-        seed = next_seed;
-        spawned.push(food);
-    }
-    (spawned, seed)
-}
-
-fn next_food_position(seed: u64, run: &GameRun, occupied_foods: &[Point]) -> (Point, u64) {
-    let width = run.board.width.max(1) as usize;
-    let height = run.board.height.max(1) as usize;
-    let total = width.saturating_mul(height).max(1);
-    let mut rng = seed;
-
-    for _ in 0..total.saturating_mul(2) {
-        rng = lcg_next(rng);
-        let idx = (rng as usize) % total;
-        let x = (idx % width) as i32;
-        let y = (idx / width) as i32;
-        let candidate = Point { x, y };
-        if !run.snake.contains(&candidate)
-            && !occupied_foods.contains(&candidate)
-            && !occupied_foods
-                .iter()
-                .any(|food| points_touch_or_adjacent(*food, candidate))
-        {
-            return (candidate, rng);
-        }
-    }
-
-    for idx in 0..total {
-        let x = (idx % width) as i32;
-        let y = (idx / width) as i32;
-        let candidate = Point { x, y };
-        if !run.snake.contains(&candidate) && !occupied_foods.contains(&candidate) {
-            return (candidate, lcg_next(rng));
-        }
-    }
-
-    (run.snake[0], lcg_next(rng))
-}
-
-fn points_touch_or_adjacent(a: Point, b: Point) -> bool {
-    (a.x - b.x).abs() <= 1 && (a.y - b.y).abs() <= 1
-}
-
-fn lcg_next(seed: u64) -> u64 {
-    seed.wrapping_mul(6364136223846793005).wrapping_add(1)
-}
-
 fn random_seed() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -1463,12 +3208,15 @@ fn next_respawn_position(seed: u64, run: &GameRun) -> (Point, u64) {
 
 #[cfg(test)]
 mod tests {
+    use super::audio::tests::RecordingAudio;
     use super::*;
-    use crate::DEFAULT_THRESHOLDS;
+    use crate::{Board, DEFAULT_PROFILE_NAME, DEFAULT_THRESHOLDS};
 
     fn unlocked_profile() -> Profile {
-        let mut profile = Profile::default();
-        profile.invincible_cumulative_length = 200;
+        let mut profile = Profile {
+            invincible_cumulative_length: 200,
+            ..Profile::default()
+        };
         profile.apply_threshold_unlocks(&crate::ToolRegistry::demo(), &DEFAULT_THRESHOLDS);
         profile
     }
@@ -1481,8 +3229,8 @@ mod tests {
         app.apply_command(UiCommand::Confirm);
         assert_eq!(app.screen, ScreenState::ModeSelect);
 
-        app.apply_command(UiCommand::Right);
-        app.apply_command(UiCommand::Right);
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Down);
         app.apply_command(UiCommand::Confirm);
         assert_eq!(app.screen, ScreenState::Loadout);
 
@@ -1505,7 +3253,7 @@ mod tests {
     #[test]
     fn direction_queue_applies_one_turn_per_tick() {
         let mut app = SnakeGuiApp::new();
-        app.start_mode(GameMode::Practice, None);
+        app.start_mode(GameMode::PRACTICE, None);
         assert_eq!(app.screen, ScreenState::Running);
 
         app.enqueue_direction(Direction::Up);
@@ -1527,8 +3275,8 @@ mod tests {
     fn gui_flow_can_start_and_complete_each_mode() {
         let mut app = SnakeGuiApp::with_profile(unlocked_profile());
 
-        for mode in MODES {
-            let requested = if mode == GameMode::Experimental {
+        for mode in app.modes().to_vec() {
+            let requested = if mode.requires_loadout() {
                 Some(vec![
                     "turn-buffer".to_string(),
                     "slow-window".to_string(),
@@ -1541,7 +3289,7 @@ mod tests {
             app.start_mode(mode, requested);
             assert_eq!(app.screen, ScreenState::Running);
 
-            if mode == GameMode::Invincible {
+            if mode == GameMode::INVINCIBLE {
                 {
                     let running = app.running.as_mut().unwrap();
                     running.run.ended = true;
@@ -1564,7 +3312,7 @@ mod tests {
     }
 
     fn enter_pointer_idle_pause(app: &mut SnakeGuiApp) {
-        app.start_mode(GameMode::Practice, None);
+        app.start_mode(GameMode::PRACTICE, None);
         assert_eq!(app.screen, ScreenState::Running);
         let outside_board = vec2(40.0, 90.0);
         app.apply_pointer_input(0.004, outside_board, 0.0);
@@ -1603,7 +3351,7 @@ mod tests {
     #[test]
     fn pointer_edge_intent_does_not_trigger_idle_pause() {
         let mut app = SnakeGuiApp::new();
-        app.start_mode(GameMode::Practice, None);
+        app.start_mode(GameMode::PRACTICE, None);
         assert_eq!(app.screen, ScreenState::Running);
 
         let hold_top_edge = vec2(470.0, 132.0);
@@ -1617,7 +3365,7 @@ mod tests {
     #[test]
     fn pointer_hover_inside_board_does_not_trigger_idle_pause() {
         let mut app = SnakeGuiApp::new();
-        app.start_mode(GameMode::Practice, None);
+        app.start_mode(GameMode::PRACTICE, None);
         assert_eq!(app.screen, ScreenState::Running);
 
         let inside_board = vec2(484.0, 306.0);
@@ -1630,7 +3378,7 @@ mod tests {
     #[test]
     fn pointer_hover_outside_board_for_10ms_triggers_idle_pause() {
         let mut app = SnakeGuiApp::new();
-        app.start_mode(GameMode::Practice, None);
+        app.start_mode(GameMode::PRACTICE, None);
         assert_eq!(app.screen, ScreenState::Running);
 
         let outside_board = vec2(40.0, 90.0);
@@ -1647,7 +3395,7 @@ mod tests {
     #[test]
     fn pointer_hover_inside_board_steers_toward_pointer() {
         let mut app = SnakeGuiApp::new();
-        app.start_mode(GameMode::Practice, None);
+        app.start_mode(GameMode::PRACTICE, None);
         assert_eq!(app.screen, ScreenState::Running);
 
         // Head starts near (5,5); hovering above it should request an Up turn.
@@ -1665,16 +3413,486 @@ mod tests {
         assert_eq!(app.running.as_ref().unwrap().phase, RunningPhase::Active);
     }
 
+    #[test]
+    fn pointer_control_off_ignores_pointer_and_never_idle_pauses() {
+        let mut app = SnakeGuiApp::new();
+        app.engine
+            .profile
+            .set_pointer_control(GameMode::PRACTICE, PointerControl::Off);
+        app.start_mode(GameMode::PRACTICE, None);
+
+        let outside_board = vec2(40.0, 90.0);
+        app.apply_pointer_input(0.5, outside_board, 0.0);
+        app.apply_pointer_input(0.5, outside_board, 0.0);
+        app.apply_pointer_input(0.5, vec2(484.0, 210.0), 0.0);
+
+        let running = app.running.as_ref().unwrap();
+        assert_eq!(running.phase, RunningPhase::Active);
+        assert!(running.queued_directions.is_empty());
+    }
+
+    #[test]
+    fn pointer_drag_steer_requires_primary_button() {
+        let mut app = SnakeGuiApp::new();
+        app.engine
+            .profile
+            .set_pointer_control(GameMode::PRACTICE, PointerControl::DragSteer);
+        app.start_mode(GameMode::PRACTICE, None);
+
+        let inside_board_up = vec2(484.0, 210.0);
+        app.apply_pointer_input(0.01, inside_board_up, 0.0);
+        assert!(app.running.as_ref().unwrap().queued_directions.is_empty());
+
+        app.pointer_primary_down = true;
+        app.apply_pointer_input(0.01, inside_board_up, 0.0);
+        assert_eq!(
+            app.running
+                .as_ref()
+                .unwrap()
+                .queued_directions
+                .front()
+                .copied(),
+            Some(Direction::Up)
+        );
+    }
+
+    #[test]
+    fn pointer_click_to_turn_steers_only_on_press() {
+        let mut app = SnakeGuiApp::new();
+        app.engine
+            .profile
+            .set_pointer_control(GameMode::PRACTICE, PointerControl::ClickToTurn);
+        app.start_mode(GameMode::PRACTICE, None);
+
+        let inside_board_up = vec2(484.0, 210.0);
+        app.pointer_primary_down = true;
+        app.apply_pointer_input(0.01, inside_board_up, 0.0);
+        assert!(app.running.as_ref().unwrap().queued_directions.is_empty());
+
+        app.pointer_primary_pressed = true;
+        app.apply_pointer_input(0.01, inside_board_up, 0.0);
+        assert_eq!(
+            app.running
+                .as_ref()
+                .unwrap()
+                .queued_directions
+                .front()
+                .copied(),
+            Some(Direction::Up)
+        );
+    }
+
+    #[test]
+    fn settings_adjust_pointer_control_per_mode_and_tuning() {
+        let mut app = SnakeGuiApp::new();
+        app.open_settings(ScreenState::MainMenu);
+
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Right);
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Left);
+        assert_eq!(
+            app.engine.profile.pointer_control(GameMode::CHALLENGE),
+            PointerControl::Off
+        );
+        assert_eq!(
+            app.engine.profile.pointer_control(GameMode::PRACTICE),
+            PointerControl::HoverSteer
+        );
+
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.engine.profile.pointer_tuning.idle_pause_ms, 20);
+    }
+
+    #[test]
+    fn interpolation_glides_segments_and_snaps_wraps() {
+        let p = |x, y| Point { x, y };
+        let previous = [p(11, 5), p(10, 5), p(9, 5)];
+        let current = [p(0, 5), p(11, 5), p(10, 5), p(9, 5)];
+        let cells = interpolate_segments(&previous, &current, 0.25);
+
+        assert_eq!(cells[0], vec2(0.0, 5.0));
+        assert_eq!(cells[1], vec2(10.25, 5.0));
+        assert_eq!(cells[3], vec2(9.0, 5.0));
+    }
+
+    #[test]
+    fn ticks_remember_previous_snake_and_render_style_toggles() {
+        let mut app = SnakeGuiApp::new();
+        app.start_mode(GameMode::PRACTICE, None);
+        let before = app.running.as_ref().unwrap().run.snake.clone();
+        app.update_running(SIM_TICK_SECONDS);
+        assert_eq!(app.running.as_ref().unwrap().previous_snake, before);
+
+        app.open_settings(ScreenState::Running);
+        app.settings_cursor = 10;
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.engine.profile.snake_render, SnakeRenderStyle::Classic);
+    }
+
+    #[test]
+    fn eating_food_spawns_effects_unless_disabled() {
+        for (intensity, expect_idle) in [
+            (EffectsIntensity::Full, false),
+            (EffectsIntensity::Off, true),
+        ] {
+            let mut app = SnakeGuiApp::new();
+            app.engine.profile.effects_intensity = intensity;
+            app.start_mode(GameMode::PRACTICE, None);
+            let state = app.running.as_mut().unwrap();
+            state.run.foods = vec![Food::new(FoodKind::Normal, Point { x: 6, y: 5 }, 0)];
+            app.update_running(SIM_TICK_SECONDS);
+
+            assert_eq!(app.running.as_ref().unwrap().run.metrics.food_eaten, 1);
+            assert!(app.running.as_ref().unwrap().run.events.is_empty());
+            assert_eq!(app.effects.is_idle(), expect_idle);
+        }
+    }
+
+    #[test]
+    fn filling_the_board_wins_and_records_a_perfect_game() {
+        let mut app = SnakeGuiApp::new();
+        app.start_mode(GameMode::PRACTICE, None);
+        let state = app.running.as_mut().unwrap();
+        state.run.board = Board {
+            width: 4,
+            height: 1,
+        };
+        state.run.snake = vec![
+            Point { x: 2, y: 0 },
+            Point { x: 1, y: 0 },
+            Point { x: 0, y: 0 },
+        ];
+        state.run.foods = vec![Food::new(FoodKind::Normal, Point { x: 3, y: 0 }, 0)];
+        app.update_running(SIM_TICK_SECONDS);
+
+        assert_eq!(app.screen, ScreenState::Summary);
+        let summary = app.summary.as_ref().unwrap();
+        assert!(summary.won && summary.perfect);
+        let row = &app.engine.leaderboards.rows(GameMode::PRACTICE)[0];
+        assert!(row.perfect);
+        assert_eq!(row.score, summary.score);
+    }
+
+    #[test]
+    fn invincible_reposition_leaves_trail_and_counts_in_summary() {
+        let mut app = SnakeGuiApp::new();
+        app.start_mode(GameMode::INVINCIBLE, None);
+        let state = app.running.as_mut().unwrap();
+        state.run.snake = vec![
+            Point { x: 11, y: 5 },
+            Point { x: 10, y: 5 },
+            Point { x: 9, y: 5 },
+        ];
+        state.run.grace_ticks_remaining = 0;
+        state.run.foods.clear();
+        app.update_running(SIM_TICK_SECONDS);
+
+        let state = app.running.as_ref().unwrap();
+        assert_eq!(state.run.grace_ticks_remaining, 2);
+        let trail = state.reposition_trail.unwrap();
+        assert_eq!(trail.from, Point { x: 11, y: 5 });
+        assert_eq!(trail.to, state.run.snake[0]);
+
+        app.pause_running();
+        app.update_running(REPOSITION_TRAIL_SECONDS);
+        assert!(app.running.as_ref().unwrap().reposition_trail.is_none());
+
+        app.complete_running_session();
+        assert_eq!(app.summary.as_ref().unwrap().repositions, 1);
+    }
+
+    #[test]
+    fn game_and_menu_events_reach_the_audio_backend() {
+        let recorder = RecordingAudio::default();
+        let mut app = SnakeGuiApp::new();
+        app.audio = SoundManager::new(Box::new(recorder.clone()));
+
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Up);
+        app.apply_command(UiCommand::Confirm);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::Running);
+        app.sync_music();
+        assert_eq!(*recorder.music.borrow(), vec![Some(GameMode::PRACTICE)]);
+
+        let state = app.running.as_mut().unwrap();
+        state.run.foods = vec![Food::new(FoodKind::Normal, Point { x: 5, y: 6 }, 0)];
+        app.apply_command(UiCommand::Down);
+        app.update_running(SIM_TICK_SECONDS);
+        let state = app.running.as_mut().unwrap();
+        state.run.grace_ticks_remaining = 0;
+        state.run.snake = vec![Point { x: 11, y: 6 }, Point { x: 11, y: 5 }];
+        app.apply_command(UiCommand::Right);
+        app.update_running(SIM_TICK_SECONDS);
+        assert_eq!(app.screen, ScreenState::Summary);
+
+        assert_eq!(
+            *recorder.sounds.borrow(),
+            vec![
+                SoundEvent::MenuMove,
+                SoundEvent::MenuMove,
+                SoundEvent::MenuConfirm,
+                SoundEvent::MenuConfirm,
+                SoundEvent::Turn,
+                SoundEvent::Eat,
+                SoundEvent::Turn,
+                SoundEvent::Collision,
+            ]
+        );
+        app.sync_music();
+        assert_eq!(recorder.music.borrow().last(), Some(&None));
+        assert_eq!(recorder.pitch_steps.borrow()[5], 0);
+    }
+
+    #[test]
+    fn settings_scroll_keeps_cursor_visible_and_hit_tests_offset_rows() {
+        let mut app = SnakeGuiApp::new();
+        app.open_settings(ScreenState::MainMenu);
+        for _ in 0..13 {
+            app.apply_command(UiCommand::Down);
+        }
+        assert_eq!(app.settings_cursor, 13);
+        assert_eq!(app.settings_scroll, 2);
+
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.engine.profile.audio_volume.sfx_percent, 100);
+        app.apply_command(UiCommand::Left);
+        assert_eq!(app.engine.profile.audio_volume.sfx_percent, 90);
+
+        let first_visible = layout::settings_row(0).hit.center();
+        assert_eq!(settings_row_at(first_visible, app.settings_scroll), Some(2));
+    }
+
+    #[test]
+    fn settings_theme_row_cycles_built_in_themes() {
+        let mut app = SnakeGuiApp::new();
+        app.open_settings(ScreenState::MainMenu);
+        app.apply_command(UiCommand::Up);
+        assert_eq!(app.settings_cursor, SETTINGS_ROW_COUNT - 1);
+
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.engine.profile.theme, ThemeId::Deuteranopia);
+        assert_eq!(app.theme(), Theme::for_id(ThemeId::Deuteranopia));
+
+        app.apply_command(UiCommand::Left);
+        app.apply_command(UiCommand::Left);
+        assert_eq!(app.engine.profile.theme, ThemeId::HighContrast);
+    }
+
+    #[test]
+    fn idle_pause_delay_follows_profile_tuning() {
+        let mut app = SnakeGuiApp::new();
+        app.engine.profile.pointer_tuning.idle_pause_ms = 500;
+        app.start_mode(GameMode::PRACTICE, None);
+
+        let outside_board = vec2(40.0, 90.0);
+        app.apply_pointer_input(0.2, outside_board, 0.0);
+        app.apply_pointer_input(0.2, outside_board, 0.0);
+        assert_eq!(app.running.as_ref().unwrap().phase, RunningPhase::Active);
+
+        app.apply_pointer_input(0.2, outside_board, 0.0);
+        assert_eq!(
+            app.running.as_ref().unwrap().phase,
+            RunningPhase::PointerIdlePause
+        );
+    }
+
+    fn press_drag_release(app: &mut SnakeGuiApp, from: Vec2, to: Vec2, drag_seconds: f32) {
+        app.pointer_primary_down = true;
+        app.pointer_primary_pressed = true;
+        app.apply_pointer_input(0.01, from, 0.0);
+        app.pointer_primary_pressed = false;
+        app.apply_pointer_input(drag_seconds, to, 0.0);
+        app.pointer_primary_down = false;
+        app.apply_pointer_input(0.01, to, 0.0);
+    }
+
+    #[test]
+    fn swipe_gesture_turns_snake_once_per_swipe() {
+        let mut app = SnakeGuiApp::new();
+        app.engine
+            .profile
+            .set_pointer_control(GameMode::PRACTICE, PointerControl::Swipe);
+        app.start_mode(GameMode::PRACTICE, None);
+
+        press_drag_release(&mut app, vec2(500.0, 600.0), vec2(505.0, 540.0), 0.05);
+
+        let queued: Vec<Direction> = app
+            .running
+            .as_ref()
+            .unwrap()
+            .queued_directions
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(queued, vec![Direction::Up]);
+    }
+
+    #[test]
+    fn slow_or_short_drags_are_not_swipes() {
+        let mut app = SnakeGuiApp::new();
+        app.engine
+            .profile
+            .set_pointer_control(GameMode::PRACTICE, PointerControl::Swipe);
+        app.start_mode(GameMode::PRACTICE, None);
+
+        press_drag_release(&mut app, vec2(500.0, 600.0), vec2(500.0, 540.0), 2.0);
+        press_drag_release(&mut app, vec2(500.0, 600.0), vec2(500.0, 590.0), 0.01);
+
+        assert!(app.running.as_ref().unwrap().queued_directions.is_empty());
+    }
+
+    #[test]
+    fn tap_confirms_focused_menu_item() {
+        let mut app = SnakeGuiApp::new();
+        press_drag_release(&mut app, vec2(110.0, 260.0), vec2(111.0, 260.0), 0.05);
+
+        assert_eq!(app.screen, ScreenState::Leaderboard);
+    }
+
+    #[test]
+    fn menu_swipe_feeds_ui_commands() {
+        let mut app = SnakeGuiApp::new();
+        press_drag_release(&mut app, vec2(700.0, 500.0), vec2(700.0, 580.0), 0.05);
+
+        assert_eq!(app.main_menu_cursor, 1);
+        assert_eq!(app.screen, ScreenState::MainMenu);
+    }
+
+    #[test]
+    fn dpad_press_enqueues_direction_without_idle_pause() {
+        let mut app = SnakeGuiApp::new();
+        app.engine.profile.show_dpad = true;
+        app.start_mode(GameMode::PRACTICE, None);
+
+        let up_button = layout::dpad_button(Direction::Up).center();
+        app.apply_pointer_input(0.5, up_button, 0.0);
+        app.apply_pointer_input(0.5, up_button, 0.0);
+        assert_eq!(app.running.as_ref().unwrap().phase, RunningPhase::Active);
+        assert!(app.running.as_ref().unwrap().queued_directions.is_empty());
+
+        app.pointer_primary_pressed = true;
+        app.apply_pointer_input(0.01, up_button, 0.0);
+        assert_eq!(
+            app.running
+                .as_ref()
+                .unwrap()
+                .queued_directions
+                .front()
+                .copied(),
+            Some(Direction::Up)
+        );
+    }
+
+    #[test]
+    fn escape_pauses_run_and_back_resumes() {
+        let mut app = SnakeGuiApp::new();
+        app.start_mode(GameMode::PRACTICE, None);
+
+        app.apply_command(UiCommand::Back);
+        assert_eq!(app.screen, ScreenState::Running);
+        assert_eq!(app.running.as_ref().unwrap().phase, RunningPhase::Paused);
+
+        let ticks_before = app.running.as_ref().unwrap().run.metrics.survival_ticks;
+        app.update_running(1.0);
+        assert_eq!(
+            app.running.as_ref().unwrap().run.metrics.survival_ticks,
+            ticks_before
+        );
+
+        app.apply_command(UiCommand::Back);
+        assert_eq!(app.running.as_ref().unwrap().phase, RunningPhase::Active);
+    }
+
+    #[test]
+    fn pause_restart_reuses_seed_and_loadout() {
+        let mut app = SnakeGuiApp::new();
+        app.start_mode_with_seed(GameMode::PRACTICE, None, 42);
+        let initial_foods = app.running.as_ref().unwrap().run.foods.clone();
+        app.step_running_tick();
+
+        app.apply_command(UiCommand::Back);
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Confirm);
+
+        let running = app.running.as_ref().unwrap();
+        assert_eq!(running.phase, RunningPhase::Active);
+        assert_eq!(running.initial_seed, 42);
+        assert_eq!(running.run.foods, initial_foods);
+        assert_eq!(running.run.metrics.survival_ticks, 0);
+        assert!(app.engine.leaderboards.rows(GameMode::PRACTICE).is_empty());
+    }
+
+    #[test]
+    fn pause_settings_returns_to_paused_run() {
+        let mut app = SnakeGuiApp::new();
+        app.start_mode(GameMode::PRACTICE, None);
+        app.apply_command(UiCommand::Back);
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::Settings);
+
+        app.apply_command(UiCommand::Back);
+        assert_eq!(app.screen, ScreenState::Running);
+        assert_eq!(app.running.as_ref().unwrap().phase, RunningPhase::Paused);
+    }
+
+    #[test]
+    fn pause_abandon_skips_leaderboard_submission() {
+        let mut app = SnakeGuiApp::new();
+        app.start_mode(GameMode::PRACTICE, None);
+        app.apply_command(UiCommand::Back);
+        app.apply_command(UiCommand::Up);
+        app.apply_command(UiCommand::Confirm);
+
+        assert_eq!(app.screen, ScreenState::Summary);
+        assert!(app.summary.as_ref().unwrap().abandoned);
+        assert!(app.engine.leaderboards.rows(GameMode::PRACTICE).is_empty());
+    }
+
+    #[test]
+    fn timed_food_expires_and_is_replaced() {
+        let mut app = SnakeGuiApp::new();
+        app.start_mode(GameMode::PRACTICE, None);
+        let state = app.running.as_mut().unwrap();
+        state.run.foods = vec![Food::new(FoodKind::Bonus, Point { x: 0, y: 0 }, 0)];
+        state.run.metrics.survival_ticks = 39;
+        app.update_running(SIM_TICK_SECONDS);
+
+        let foods = &app.running.as_ref().unwrap().run.foods;
+        assert_eq!(foods.len(), 1);
+        assert_ne!(foods[0].expires_at_tick, Some(40));
+    }
+
+    #[test]
+    fn speed_up_food_shortens_ticks_until_it_wears_off() {
+        let mut app = SnakeGuiApp::new();
+        app.start_mode(GameMode::PRACTICE, None);
+        let state = app.running.as_mut().unwrap();
+        state.run.foods = vec![Food::new(FoodKind::SpeedUp, Point { x: 6, y: 5 }, 0)];
+        app.update_running(SIM_TICK_SECONDS);
+
+        let state = app.running.as_ref().unwrap();
+        assert_eq!(state.run.snake.len(), 4);
+        assert!(state.effective_tick_seconds() < SIM_TICK_SECONDS);
+    }
+
     #[test]
     fn initial_foods_are_non_contiguous() {
         let mut app = SnakeGuiApp::new();
-        app.start_mode(GameMode::Practice, None);
-        let foods = &app.running.as_ref().unwrap().foods;
-        assert_eq!(foods.len(), INITIAL_FOOD_COUNT);
+        app.start_mode(GameMode::PRACTICE, None);
+        let foods = &app.running.as_ref().unwrap().run.foods;
+        assert_eq!(foods.len(), crate::FoodRules::multi_food().initial_count);
 
         for (i, food_a) in foods.iter().enumerate() {
             for food_b in foods.iter().skip(i + 1) {
-                assert!(!points_touch_or_adjacent(*food_a, *food_b));
+                assert!(!crate::food::points_touch_or_adjacent(
+                    food_a.position,
+                    food_b.position
+                ));
             }
         }
     }
@@ -1702,6 +3920,351 @@ mod tests {
         assert_eq!(app.mode_cursor, 0);
     }
 
+    #[test]
+    fn mode_select_picks_difficulty_and_leaderboard_filters_by_it() {
+        let mut app = SnakeGuiApp::new();
+        app.screen = ScreenState::ModeSelect;
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.engine.profile.difficulty, Difficulty::Hard);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(
+            app.running.as_ref().unwrap().run.difficulty,
+            Difficulty::Hard
+        );
+        app.running.as_mut().unwrap().run.abandoned = false;
+        app.complete_running_session();
+        assert_eq!(app.summary.as_ref().unwrap().difficulty, Difficulty::Hard);
+
+        app.screen = ScreenState::Leaderboard;
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.leaderboard_filter.difficulty, Some(Difficulty::Easy));
+        assert!(app.leaderboard_rows().is_empty());
+        app.apply_command(UiCommand::Left);
+        app.apply_command(UiCommand::Left);
+        assert_eq!(app.leaderboard_filter.difficulty, Some(Difficulty::Insane));
+        assert_eq!(app.leaderboard_mode_cursor, 0);
+        assert_eq!(
+            ranking_label(&GameMode::TIME_ATTACK.policy().ranking()),
+            "highest score, then quickest run, then earliest run"
+        );
+    }
+
+    #[test]
+    fn profiles_are_created_by_name_and_switched_from_the_menu() {
+        let mut app = SnakeGuiApp::new();
+        app.engine.profile.theme = ThemeId::ALL[1];
+        app.main_menu_cursor = 3;
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::ProfileSelect);
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::NameEntry);
+
+        for typed in "Zoe!\u{8}".chars() {
+            app.type_name_char(typed);
+        }
+        app.apply_command(UiCommand::Confirm);
+        assert!(app.message.take().unwrap().contains("'!'"));
+        app.apply_command(UiCommand::Left);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::MainMenu);
+        assert_eq!(app.engine.profile.name, "Zoe");
+        assert_eq!(app.engine.profile.theme, ThemeId::default());
+
+        app.start_mode(GameMode::PRACTICE, None);
+        app.running.as_mut().unwrap().run.abandoned = false;
+        app.complete_running_session();
+        assert_eq!(
+            app.engine.leaderboards.rows(GameMode::PRACTICE)[0].player,
+            "Zoe"
+        );
+
+        app.open_profile_select();
+        assert_eq!(app.profile_cursor, 1);
+        app.apply_command(UiCommand::Up);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.engine.profile.name, DEFAULT_PROFILE_NAME);
+        assert_eq!(app.engine.profile.theme, ThemeId::ALL[1]);
+        assert!(app.summary.is_none());
+    }
+
+    #[test]
+    fn leaderboard_files_export_and_import_between_apps() {
+        let dir = std::env::temp_dir().join(format!("snake-gui-export-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut home = SnakeGuiApp::new();
+        home.start_mode(GameMode::PRACTICE, None);
+        home.running.as_mut().unwrap().run.abandoned = false;
+        home.complete_running_session();
+
+        home.export_dir = dir.clone();
+        home.apply_command(UiCommand::Right);
+        home.leaderboard_focus = 6;
+        home.apply_command(UiCommand::Confirm);
+        assert_eq!(home.screen, ScreenState::Leaderboard);
+        assert!(home.message.take().unwrap().starts_with("Exported 1 rows"));
+        assert!(dir.join("leaderboard-practice.csv").exists());
+
+        let mut away = SnakeGuiApp::new();
+        away.export_dir = dir.clone();
+        away.open_leaderboard(None);
+        away.leaderboard_focus = 6;
+        away.apply_command(UiCommand::Left);
+        away.apply_command(UiCommand::Left);
+        assert_eq!(
+            LEADERBOARD_FILE_ACTIONS[away.leaderboard_file_action].0,
+            "import CSV"
+        );
+        away.apply_command(UiCommand::Confirm);
+        assert!(
+            away.message
+                .take()
+                .unwrap()
+                .starts_with("Imported 1 rows, skipped 0")
+        );
+        away.apply_command(UiCommand::Confirm);
+        assert!(
+            away.message
+                .take()
+                .unwrap()
+                .contains("skipped 1 duplicates")
+        );
+        assert_eq!(
+            away.engine.leaderboards.rows(GameMode::PRACTICE),
+            home.engine.leaderboards.rows(GameMode::PRACTICE)
+        );
+
+        away.apply_command(UiCommand::Right);
+        away.apply_command(UiCommand::Confirm);
+        assert!(
+            away.message
+                .take()
+                .unwrap()
+                .contains("leaderboard-practice.json")
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn leaderboard_pages_filters_and_highlights_the_last_run() {
+        let mut app = SnakeGuiApp::new();
+        for score in 0..23 {
+            app.engine.leaderboards.submit(LeaderboardEntry {
+                mode: GameMode::EXPERIMENTAL,
+                score,
+                survival_ticks: 100,
+                loadout_summary: if score % 2 == 0 { "a+b+c" } else { "d+e+f" }.to_string(),
+                perfect: false,
+                difficulty: Difficulty::Normal,
+                preset: None,
+                food_eaten: score,
+                growth_units: score,
+                // The lowest score is ancient; the rest finished just now.
+                recorded_at: if score == 0 { 0 } else { unix_now() },
+                board: Board {
+                    width: 12 + (score % 3) as i32,
+                    height: 12,
+                },
+                run_id: 1_000 + score,
+                player: "Ana".to_string(),
+            });
+        }
+        app.leaderboard_mode_cursor = app.mode_index(GameMode::EXPERIMENTAL);
+        app.open_leaderboard(None);
+        assert_eq!(app.leaderboard_page_count(), 3);
+        app.apply_command(UiCommand::Up);
+        assert_eq!(LEADERBOARD_CONTROLS[app.leaderboard_focus], "Page");
+        app.apply_command(UiCommand::Left);
+        assert_eq!(app.leaderboard_page, 2);
+
+        app.leaderboard_focus = 3;
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.leaderboard_page, 0);
+        let rows = app.leaderboard_rows();
+        assert_eq!(rows.len(), 8);
+        assert!(rows.iter().all(|row| row.board.width == 12));
+
+        app.leaderboard_focus = 4;
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.leaderboard_rows().len(), 7);
+
+        app.leaderboard_focus = 5;
+        app.apply_command(UiCommand::Right);
+        let best: Vec<u64> = app.leaderboard_rows().iter().map(|row| row.score).collect();
+        assert_eq!(best, [21, 18]);
+        app.leaderboard_focus = 2;
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.leaderboard_filter.loadout.as_deref(), Some("a+b+c"));
+        assert_eq!(app.leaderboard_rows().len(), 1);
+
+        app.start_mode(GameMode::PRACTICE, None);
+        app.running.as_mut().unwrap().run.abandoned = false;
+        app.complete_running_session();
+        let run_id = app.summary.as_ref().unwrap().run_id;
+        assert!(run_id.is_some());
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.screen, ScreenState::Leaderboard);
+        assert_eq!(app.leaderboard_filter, LeaderboardFilter::default());
+        assert_eq!(app.leaderboard_highlight, run_id);
+        let position = app
+            .leaderboard_rows()
+            .iter()
+            .position(|row| Some(row.run_id) == run_id)
+            .unwrap();
+        assert_eq!(app.leaderboard_page, position / LEADERBOARD_PAGE_ROWS);
+    }
+
+    #[test]
+    fn time_attack_ends_on_the_clock_with_a_summary() {
+        let mut app = SnakeGuiApp::new();
+        app.engine.profile.difficulty = Difficulty::Insane;
+        app.start_mode(GameMode::TIME_ATTACK, None);
+        let state = app.running.as_mut().unwrap();
+        let budget = crate::TIME_ATTACK_BUDGET_TICKS as f32 * SIM_TICK_SECONDS;
+        assert!((state.time_remaining_seconds().unwrap() - budget / 1.45).abs() < 0.01);
+        state.run.time_remaining_ticks = Some(2);
+        state.run.grace_ticks_remaining = 5;
+        state.run.foods.clear();
+        app.update_running(SIM_TICK_SECONDS);
+        assert_eq!(app.screen, ScreenState::Running);
+        app.update_running(SIM_TICK_SECONDS);
+
+        assert_eq!(app.screen, ScreenState::Summary);
+        assert!(app.summary.as_ref().unwrap().timed_out);
+        assert_eq!(app.engine.leaderboards.rows(GameMode::TIME_ATTACK).len(), 1);
+    }
+
+    #[test]
+    fn puzzle_picked_from_level_select_can_be_solved_at_par() {
+        let mut app = SnakeGuiApp::new();
+        app.screen = ScreenState::ModeSelect;
+        app.mode_cursor = app.mode_index(GameMode::PUZZLE);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::PuzzleSelect);
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::Running);
+        assert_eq!(
+            app.running.as_ref().unwrap().run.walls,
+            PUZZLES[1].walls.to_vec()
+        );
+
+        for (turn, moves) in [
+            (UiCommand::Down, 3),
+            (UiCommand::Right, 4),
+            (UiCommand::Up, 3),
+        ] {
+            app.apply_command(turn);
+            for _ in 0..moves {
+                app.update_running(SIM_TICK_SECONDS);
+            }
+        }
+
+        assert_eq!(app.screen, ScreenState::Summary);
+        assert_eq!(app.summary.as_ref().unwrap().puzzle_stars, Some(3));
+        assert_eq!(app.engine.profile.puzzle_stars(PUZZLES[1].id), 3);
+    }
+
+    #[test]
+    fn campaign_map_gates_locked_stages_and_records_clears() {
+        let mut app = SnakeGuiApp::new();
+        app.screen = ScreenState::ModeSelect;
+        app.mode_cursor = app.mode_index(GameMode::CAMPAIGN);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::CampaignMap);
+        assert_eq!(app.campaign_cursor, 0);
+
+        app.apply_command(UiCommand::Right);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::CampaignMap);
+        assert!(app.message.is_some());
+
+        app.apply_command(UiCommand::Left);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::Running);
+        app.running.as_mut().unwrap().run.metrics.food_eaten = 5;
+        // Meadow runs below base speed, so a move takes longer than one sim tick.
+        app.update_running(SIM_TICK_SECONDS);
+        app.update_running(SIM_TICK_SECONDS);
+
+        assert_eq!(app.screen, ScreenState::Summary);
+        assert_eq!(app.summary.as_ref().unwrap().cleared_stage, Some(0));
+        assert!(app.engine.profile.campaign_stage_unlocked(1));
+    }
+
+    #[test]
+    fn arena_roamer_in_the_way_ends_the_run() {
+        let mut app = SnakeGuiApp::new();
+        app.start_mode(GameMode::ARENA, None);
+        let state = app.running.as_mut().unwrap();
+        state.run.grace_ticks_remaining = 0;
+        state.run.foods.clear();
+        let head = state.run.snake[0];
+        state
+            .run
+            .arena
+            .as_mut()
+            .unwrap()
+            .roamers
+            .push(crate::Roamer {
+                position: Point {
+                    x: head.x + 1,
+                    y: head.y,
+                },
+                dx: 1,
+                dy: 1,
+            });
+        app.update_running(SIM_TICK_SECONDS);
+
+        assert_eq!(app.screen, ScreenState::Summary);
+        assert_eq!(app.engine.leaderboards.rows(GameMode::ARENA).len(), 1);
+    }
+
+    #[test]
+    fn custom_builder_saves_presets_and_plays_them() {
+        let mut app = SnakeGuiApp::new();
+        app.screen = ScreenState::ModeSelect;
+        app.mode_cursor = app.mode_index(GameMode::CUSTOM);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::CustomBuilder);
+        assert_eq!(app.custom_draft.name, "Custom 1");
+
+        app.apply_command(UiCommand::Down);
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.custom_draft.collision, CollisionOutcome::Reposition);
+        app.custom_cursor = 5;
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.custom_draft.weights.per_food, 15);
+        app.custom_cursor = CUSTOM_FIXED_ROWS;
+        app.apply_command(UiCommand::Confirm);
+        assert!(app.custom_draft.tools.is_empty());
+
+        let rows = app.custom_row_count();
+        app.custom_cursor = rows - 2;
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(
+            app.engine.profile.custom_presets,
+            vec![app.custom_draft.clone()]
+        );
+
+        app.custom_cursor = 0;
+        app.apply_command(UiCommand::Right);
+        assert_eq!(app.custom_draft.name, "Custom 2");
+        app.apply_command(UiCommand::Left);
+        assert_eq!(app.custom_draft.weights.per_food, 15);
+
+        app.custom_cursor = rows - 1;
+        app.apply_command(UiCommand::Confirm);
+        assert_eq!(app.screen, ScreenState::Running);
+        let run = &app.running.as_ref().unwrap().run;
+        assert_eq!(run.mode, GameMode::CUSTOM);
+        assert_eq!(
+            run.custom.as_ref().unwrap().collision,
+            CollisionOutcome::Reposition
+        );
+    }
+
     #[test]
     fn gui_menu_has_no_daily_weekly_or_literary_surfaces() {
         assert!(MAIN_MENU_ITEMS.iter().all(|item| !item.contains("Daily")
@@ -0,0 +1,237 @@
+//! Sound effects and per-mode music behind a swappable backend.
+//!
+//! [`SoundManager`] decides what to play and at which gain; an
+//! [`AudioBackend`] does the actual output. [`NullAudio`] keeps tests and
+//! headless builds silent, while [`MacroquadAudio`] plays effects rendered by
+//! the synthesizer plus any music loops it finds on disk.
+
+use std::collections::HashMap;
+
+use macroquad::audio::{
+    PlaySoundParams, Sound, load_sound, load_sound_from_bytes, play_sound, set_sound_volume,
+    stop_sound,
+};
+
+use super::synth::{self, PITCH_STEPS};
+use crate::{AudioVolume, GameMode};
+
+const AUDIO_DIR: &str = "assets/audio";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum SoundEvent {
+    Turn,
+    Eat,
+    Collision,
+    Reposition,
+    Unlock,
+    MenuMove,
+    MenuConfirm,
+}
+
+impl SoundEvent {
+    pub const ALL: [SoundEvent; 7] = [
+        SoundEvent::Turn,
+        SoundEvent::Eat,
+        SoundEvent::Collision,
+        SoundEvent::Reposition,
+        SoundEvent::Unlock,
+        SoundEvent::MenuMove,
+        SoundEvent::MenuConfirm,
+    ];
+}
+
+pub(super) trait AudioBackend {
+    /// `pitch_step` selects a semitone-transposed variant of pitched cues.
+    fn play_sound(&mut self, event: SoundEvent, pitch_step: usize, gain: f32);
+    /// Starts looping `mode`'s track, or stops music when `None`.
+    fn play_music(&mut self, mode: Option<GameMode>, gain: f32);
+    fn set_music_gain(&mut self, gain: f32);
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct NullAudio;
+
+impl AudioBackend for NullAudio {
+    fn play_sound(&mut self, _event: SoundEvent, _pitch_step: usize, _gain: f32) {}
+    fn play_music(&mut self, _mode: Option<GameMode>, _gain: f32) {}
+    fn set_music_gain(&mut self, _gain: f32) {}
+}
+
+/// Plays synthesized effects and, when present, `assets/audio/music-<mode>.ogg`
+/// loops.
+pub(super) struct MacroquadAudio {
+    sounds: HashMap<(SoundEvent, usize), Sound>,
+    music: HashMap<GameMode, Sound>,
+    playing: Option<GameMode>,
+}
+
+impl MacroquadAudio {
+    pub async fn load(modes: &[GameMode]) -> Self {
+        let mut sounds = HashMap::new();
+        for event in SoundEvent::ALL {
+            let steps = if synth::is_pitched(event) {
+                PITCH_STEPS
+            } else {
+                1
+            };
+            for step in 0..steps {
+                let wav = synth::encode_wav(&synth::render(&synth::patch(event, step)));
+                if let Ok(sound) = load_sound_from_bytes(&wav).await {
+                    sounds.insert((event, step), sound);
+                }
+            }
+        }
+        let mut music = HashMap::new();
+        for mode in modes.iter().copied() {
+            let path = format!("{AUDIO_DIR}/music-{}.ogg", mode.id());
+            if let Ok(sound) = load_sound(&path).await {
+                music.insert(mode, sound);
+            }
+        }
+        Self {
+            sounds,
+            music,
+            playing: None,
+        }
+    }
+}
+
+impl AudioBackend for MacroquadAudio {
+    fn play_sound(&mut self, event: SoundEvent, pitch_step: usize, gain: f32) {
+        let sound = self
+            .sounds
+            .get(&(event, pitch_step))
+            .or_else(|| self.sounds.get(&(event, 0)));
+        if let Some(sound) = sound {
+            play_sound(
+                sound,
+                PlaySoundParams {
+                    looped: false,
+                    volume: gain,
+                },
+            );
+        }
+    }
+
+    fn play_music(&mut self, mode: Option<GameMode>, gain: f32) {
+        if let Some(track) = self.playing.take().and_then(|mode| self.music.get(&mode)) {
+            stop_sound(track);
+        }
+        if let Some(track) = mode.and_then(|mode| self.music.get(&mode)) {
+            play_sound(
+                track,
+                PlaySoundParams {
+                    looped: true,
+                    volume: gain,
+                },
+            );
+        }
+        self.playing = mode;
+    }
+
+    fn set_music_gain(&mut self, gain: f32) {
+        if let Some(track) = self.playing.and_then(|mode| self.music.get(&mode)) {
+            set_sound_volume(track, gain);
+        }
+    }
+}
+
+pub(super) struct SoundManager {
+    backend: Box<dyn AudioBackend>,
+    music: Option<GameMode>,
+    music_gain: f32,
+}
+
+impl Default for SoundManager {
+    fn default() -> Self {
+        Self::new(Box::new(NullAudio))
+    }
+}
+
+impl SoundManager {
+    pub fn new(backend: Box<dyn AudioBackend>) -> Self {
+        Self {
+            backend,
+            music: None,
+            music_gain: 0.0,
+        }
+    }
+
+    pub fn play(&mut self, event: SoundEvent, volume: &AudioVolume) {
+        self.play_pitched(event, 0, volume);
+    }
+
+    pub fn play_pitched(&mut self, event: SoundEvent, pitch_step: usize, volume: &AudioVolume) {
+        let gain = volume.sfx_gain();
+        if gain > 0.0 {
+            self.backend.play_sound(event, pitch_step, gain);
+        }
+    }
+
+    /// Switches tracks only when the wanted track changes and forwards volume
+    /// changes, so it is cheap to call every frame.
+    pub fn sync_music(&mut self, mode: Option<GameMode>, volume: &AudioVolume) {
+        let gain = volume.music_gain();
+        if mode != self.music {
+            self.backend.play_music(mode, gain);
+            self.music = mode;
+        } else if gain != self.music_gain {
+            self.backend.set_music_gain(gain);
+        }
+        self.music_gain = gain;
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+
+    /// Backend that logs every call so GUI tests can assert on audio cues.
+    #[derive(Debug, Clone, Default)]
+    pub struct RecordingAudio {
+        pub sounds: Rc<RefCell<Vec<SoundEvent>>>,
+        pub pitch_steps: Rc<RefCell<Vec<usize>>>,
+        pub music: Rc<RefCell<Vec<Option<GameMode>>>>,
+    }
+
+    impl AudioBackend for RecordingAudio {
+        fn play_sound(&mut self, event: SoundEvent, pitch_step: usize, _gain: f32) {
+            self.sounds.borrow_mut().push(event);
+            self.pitch_steps.borrow_mut().push(pitch_step);
+        }
+
+        fn play_music(&mut self, mode: Option<GameMode>, _gain: f32) {
+            self.music.borrow_mut().push(mode);
+        }
+
+        fn set_music_gain(&mut self, _gain: f32) {}
+    }
+
+    #[test]
+    fn muted_effects_never_reach_the_backend() {
+        let recorder = RecordingAudio::default();
+        let mut manager = SoundManager::new(Box::new(recorder.clone()));
+        let mut volume = AudioVolume::default();
+        manager.play(SoundEvent::Eat, &volume);
+        volume.sfx_percent = 0;
+        manager.play(SoundEvent::Eat, &volume);
+        assert_eq!(*recorder.sounds.borrow(), vec![SoundEvent::Eat]);
+    }
+
+    #[test]
+    fn music_switches_only_when_track_changes() {
+        let recorder = RecordingAudio::default();
+        let mut manager = SoundManager::new(Box::new(recorder.clone()));
+        let volume = AudioVolume::default();
+        manager.sync_music(Some(GameMode::PRACTICE), &volume);
+        manager.sync_music(Some(GameMode::PRACTICE), &volume);
+        manager.sync_music(None, &volume);
+        assert_eq!(
+            *recorder.music.borrow(),
+            vec![Some(GameMode::PRACTICE), None]
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Particles, screen shake and flash overlays spawned from [`RunEvent`]s.
+//!
+//! Everything lives in design coordinates and is advanced with frame time, so
+//! effects keep animating while the simulation is paused between ticks.
+
+use macroquad::prelude::*;
+
+use super::layout::{BoardLayout, DESIGN_HEIGHT, DESIGN_WIDTH, Layout};
+use super::lcg_next;
+use super::theme::Theme;
+use crate::{EffectsIntensity, Point, RunEvent};
+
+const PARTICLE_GRAVITY: f32 = 260.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Particle {
+    position: Vec2,
+    velocity: Vec2,
+    life: f32,
+    max_life: f32,
+    radius: f32,
+    color: Color,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Flash {
+    color: Color,
+    remaining: f32,
+    duration: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct Effects {
+    particles: Vec<Particle>,
+    shake_remaining: f32,
+    shake_strength: f32,
+    flash: Option<Flash>,
+    seed: u64,
+}
+
+impl Effects {
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.shake_remaining = 0.0;
+        self.flash = None;
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.particles.is_empty() && self.shake_remaining <= 0.0 && self.flash.is_none()
+    }
+
+    pub fn trigger(
+        &mut self,
+        event: RunEvent,
+        intensity: EffectsIntensity,
+        board: &BoardLayout,
+        theme: &Theme,
+    ) {
+        // Low keeps sparse particles but drops motion and full-screen flashes.
+        let (particle_scale, motion) = match intensity {
+            EffectsIntensity::Off => return,
+            EffectsIntensity::Low => (0.35, false),
+            EffectsIntensity::Full => (1.0, true),
+        };
+        let center = |point: Point| board.cell_origin(point.x as f32 + 0.5, point.y as f32 + 0.5);
+        let count = |base: f32| ((base * particle_scale).round() as usize).max(1);
+
+        match event {
+            RunEvent::FoodEaten { at, kind } => {
+                self.burst(center(at), count(12.0), 140.0, theme.food_color(kind));
+            }
+            RunEvent::GraceStarted { .. } => {
+                if motion {
+                    self.flash(theme.highlight, 0.25);
+                }
+            }
+            RunEvent::Repositioned { from, to } => {
+                self.burst(center(from), count(10.0), 90.0, theme.ghost);
+                self.burst(center(to), count(16.0), 160.0, theme.accent);
+                if motion {
+                    self.shake(0.15, 3.0);
+                }
+            }
+            RunEvent::BoardFilled { .. } => {
+                self.burst(board.frame().center(), count(40.0), 320.0, theme.highlight);
+                if motion {
+                    self.flash(theme.highlight, 0.5);
+                }
+            }
+            RunEvent::PuzzleSolved { .. } | RunEvent::StageCleared { .. } => {
+                self.burst(board.frame().center(), count(30.0), 260.0, theme.highlight);
+            }
+            RunEvent::OutOfMoves => {
+                if motion {
+                    self.flash(theme.danger, 0.3);
+                }
+            }
+            RunEvent::HazardAppeared { at } => {
+                self.burst(center(at), count(8.0), 80.0, theme.danger);
+            }
+            RunEvent::BorderClosed { .. } => {
+                if motion {
+                    self.shake(0.2, 4.0);
+                    self.flash(theme.danger, 0.2);
+                }
+            }
+            RunEvent::TimeUp => {
+                if motion {
+                    self.flash(theme.accent, 0.4);
+                }
+            }
+            RunEvent::Died { at } => {
+                self.burst(center(at), count(24.0), 220.0, theme.danger);
+                if motion {
+                    self.shake(0.35, 7.0);
+                    self.flash(theme.danger, 0.3);
+                }
+            }
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        if self.is_idle() {
+            return;
+        }
+        for particle in &mut self.particles {
+            particle.life -= dt;
+            particle.velocity.y += PARTICLE_GRAVITY * dt;
+            particle.position += particle.velocity * dt;
+        }
+        self.particles.retain(|particle| particle.life > 0.0);
+
+        self.shake_remaining = (self.shake_remaining - dt).max(0.0);
+        if let Some(flash) = self.flash.as_mut() {
+            flash.remaining -= dt;
+            if flash.remaining <= 0.0 {
+                self.flash = None;
+            }
+        }
+    }
+
+    /// Offset to apply to the playfield this frame, in design pixels.
+    pub fn shake_offset(&self) -> Vec2 {
+        if self.shake_remaining <= 0.0 {
+            return Vec2::ZERO;
+        }
+        let phase = self.shake_remaining * 90.0;
+        vec2(phase.sin(), (phase * 1.3).cos()) * self.shake_strength
+    }
+
+    /// Draws particles on the shaken playfield and any flash over the whole canvas.
+    pub fn draw(&self, ui: &Layout) {
+        let playfield = ui.translated(self.shake_offset());
+        for particle in &self.particles {
+            let fade = (particle.life / particle.max_life).clamp(0.0, 1.0);
+            let mut color = particle.color;
+            color.a *= fade;
+            playfield.circle(particle.position, particle.radius * fade.max(0.3), color);
+        }
+        if let Some(flash) = self.flash {
+            let mut color = flash.color;
+            color.a = 0.35 * (flash.remaining / flash.duration).clamp(0.0, 1.0);
+            ui.fill(Rect::new(0.0, 0.0, DESIGN_WIDTH, DESIGN_HEIGHT), color);
+        }
+    }
+
+    fn burst(&mut self, origin: Vec2, count: usize, speed: f32, color: Color) {
+        for i in 0..count {
+            self.seed = lcg_next(self.seed);
+            let jitter = (self.seed >> 40) as f32 / (1u64 << 24) as f32;
+            let angle = (i as f32 + jitter) / count as f32 * std::f32::consts::TAU;
+            let life = 0.35 + 0.3 * jitter;
+            self.particles.push(Particle {
+                position: origin,
+                velocity: vec2(angle.cos(), angle.sin()) * speed * (0.6 + 0.4 * jitter),
+                life,
+                max_life: life,
+                radius: 3.0 + 2.0 * jitter,
+                color,
+            });
+        }
+    }
+
+    fn shake(&mut self, duration: f32, strength: f32) {
+        self.shake_strength = if self.shake_remaining > 0.0 {
+            self.shake_strength.max(strength)
+        } else {
+            strength
+        };
+        self.shake_remaining = self.shake_remaining.max(duration);
+    }
+
+    fn flash(&mut self, color: Color, duration: f32) {
+        self.flash = Some(Flash {
+            color,
+            remaining: duration,
+            duration,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Board, ThemeId};
+
+    fn board() -> BoardLayout {
+        super::super::layout::board(Board {
+            width: 12,
+            height: 12,
+        })
+    }
+
+    #[test]
+    fn off_intensity_spawns_nothing() {
+        let mut effects = Effects::default();
+        let theme = Theme::for_id(ThemeId::Classic);
+        effects.trigger(
+            RunEvent::Died {
+                at: Point { x: 1, y: 1 },
+            },
+            EffectsIntensity::Off,
+            &board(),
+            &theme,
+        );
+        assert!(effects.is_idle());
+    }
+
+    #[test]
+    fn death_shakes_and_flashes_then_settles() {
+        let mut effects = Effects::default();
+        let theme = Theme::for_id(ThemeId::Classic);
+        effects.trigger(
+            RunEvent::Died {
+                at: Point { x: 1, y: 1 },
+            },
+            EffectsIntensity::Full,
+            &board(),
+            &theme,
+        );
+        assert_eq!(effects.particles.len(), 24);
+        assert!(effects.flash.is_some());
+        effects.update(0.01);
+        assert_ne!(effects.shake_offset(), Vec2::ZERO);
+
+        for _ in 0..100 {
+            effects.update(0.02);
+        }
+        assert!(effects.is_idle());
+        assert_eq!(effects.shake_offset(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn low_intensity_thins_particles_without_motion() {
+        let mut effects = Effects::default();
+        let theme = Theme::for_id(ThemeId::Classic);
+        effects.trigger(
+            RunEvent::Died {
+                at: Point { x: 1, y: 1 },
+            },
+            EffectsIntensity::Low,
+            &board(),
+            &theme,
+        );
+        assert!(effects.particles.len() < 24);
+        assert!(effects.flash.is_none());
+        assert_eq!(effects.shake_offset(), Vec2::ZERO);
+    }
+}
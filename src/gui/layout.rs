@@ -0,0 +1,302 @@
+//! Screen layout shared by drawing and pointer hit-testing.
+//!
+//! Every screen is authored on a fixed design canvas. [`Layout`] scales that
+//! canvas uniformly to the current window and centers it on the spare axis, so
+//! the rect used to draw a widget is the same rect the pointer is tested
+//! against.
+
+use macroquad::prelude::*;
+
+use super::Direction;
+use crate::{Board, Point};
+
+pub(super) const DESIGN_WIDTH: f32 = 1000.0;
+pub(super) const DESIGN_HEIGHT: f32 = 760.0;
+
+const BOARD_AREA: Rect = Rect::new(40.0, 130.0, 920.0, 510.0);
+const MAX_CELL_SIZE: f32 = 32.0;
+const DPAD_CENTER: Vec2 = vec2(860.0, 420.0);
+const DPAD_BUTTON_SIZE: f32 = 64.0;
+
+/// A labelled row: the rect the pointer hits and the baseline its text starts at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Slot {
+    pub hit: Rect,
+    pub text: Vec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct BoardLayout {
+    pub origin: Vec2,
+    pub cell: f32,
+    pub columns: i32,
+    pub rows: i32,
+}
+
+impl BoardLayout {
+    pub fn frame(&self) -> Rect {
+        Rect::new(
+            self.origin.x,
+            self.origin.y,
+            self.columns as f32 * self.cell,
+            self.rows as f32 * self.cell,
+        )
+    }
+
+    pub fn cell_origin(&self, x: f32, y: f32) -> Vec2 {
+        self.origin + vec2(x, y) * self.cell
+    }
+
+    pub fn cell_rect(&self, point: Point) -> Rect {
+        let origin = self.cell_origin(point.x as f32, point.y as f32);
+        Rect::new(origin.x, origin.y, self.cell, self.cell)
+    }
+
+    pub fn cell_at(&self, position: Vec2) -> Option<Point> {
+        if !self.frame().contains(position) {
+            return None;
+        }
+        let local = (position - self.origin) / self.cell;
+        Some(Point {
+            x: local.x.floor() as i32,
+            y: local.y.floor() as i32,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Layout {
+    scale: f32,
+    offset: Vec2,
+}
+
+impl Layout {
+    pub fn for_screen(width: f32, height: f32) -> Self {
+        let scale = (width / DESIGN_WIDTH).min(height / DESIGN_HEIGHT).max(0.01);
+        let offset = vec2(
+            (width - DESIGN_WIDTH * scale) / 2.0,
+            (height - DESIGN_HEIGHT * scale) / 2.0,
+        );
+        Self { scale, offset }
+    }
+
+    #[cfg(test)]
+    pub fn current() -> Self {
+        Self::for_screen(DESIGN_WIDTH, DESIGN_HEIGHT)
+    }
+
+    #[cfg(not(test))]
+    pub fn current() -> Self {
+        Self::for_screen(screen_width(), screen_height())
+    }
+
+    /// The same mapping with the design canvas nudged by `delta` design pixels.
+    pub fn translated(self, delta: Vec2) -> Self {
+        Self {
+            scale: self.scale,
+            offset: self.offset + delta * self.scale,
+        }
+    }
+
+    pub fn to_design(self, screen: Vec2) -> Vec2 {
+        (screen - self.offset) / self.scale
+    }
+
+    pub fn to_screen(self, design: Vec2) -> Vec2 {
+        self.offset + design * self.scale
+    }
+
+    pub fn rect(&self, design: Rect) -> Rect {
+        let origin = self.to_screen(design.point());
+        Rect::new(
+            origin.x,
+            origin.y,
+            design.w * self.scale,
+            design.h * self.scale,
+        )
+    }
+
+    pub fn text(&self, text: &str, x: f32, y: f32, size: f32, color: Color) {
+        let at = self.to_screen(vec2(x, y));
+        draw_text(text, at.x, at.y, size * self.scale, color);
+    }
+
+    pub fn fill(&self, design: Rect, color: Color) {
+        let rect = self.rect(design);
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, color);
+    }
+
+    pub fn circle(&self, center: Vec2, radius: f32, color: Color) {
+        let at = self.to_screen(center);
+        draw_circle(at.x, at.y, radius * self.scale, color);
+    }
+
+    pub fn line(&self, from: Vec2, to: Vec2, thickness: f32, color: Color) {
+        let (from, to) = (self.to_screen(from), self.to_screen(to));
+        draw_line(from.x, from.y, to.x, to.y, thickness * self.scale, color);
+    }
+
+    pub fn outline(&self, design: Rect, thickness: f32, color: Color) {
+        let rect = self.rect(design);
+        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, thickness, color);
+    }
+}
+
+pub(super) fn back_hotzone() -> Rect {
+    Rect::new(16.0, 18.0, 120.0, 54.0)
+}
+
+pub(super) fn main_menu_item(index: usize) -> Slot {
+    let y = 210.0 + index as f32 * 50.0;
+    Slot {
+        hit: Rect::new(80.0, y - 36.0, 400.0, 48.0),
+        text: vec2(100.0, y),
+    }
+}
+
+pub(super) fn mode_item(index: usize) -> Slot {
+    let y = 190.0 + index as f32 * 42.0;
+    Slot {
+        hit: Rect::new(80.0, y - 32.0, 440.0, 40.0),
+        text: vec2(100.0, y),
+    }
+}
+
+pub(super) fn puzzle_item(index: usize) -> Slot {
+    let y = 200.0 + index as f32 * 52.0;
+    Slot {
+        hit: Rect::new(80.0, y - 36.0, 840.0, 48.0),
+        text: vec2(100.0, y),
+    }
+}
+
+/// Campaign stages zig-zag left to right; `hit` is the node's square.
+pub(super) fn campaign_node(index: usize) -> Slot {
+    let center = vec2(
+        140.0 + index as f32 * 180.0,
+        if index.is_multiple_of(2) {
+            260.0
+        } else {
+            360.0
+        },
+    );
+    Slot {
+        hit: Rect::new(center.x - 36.0, center.y - 36.0, 72.0, 72.0),
+        text: center,
+    }
+}
+
+pub(super) fn loadout_slot(index: usize) -> Slot {
+    let y = 230.0 + index as f32 * 90.0;
+    Slot {
+        hit: Rect::new(80.0, y - 42.0, 830.0, 62.0),
+        text: vec2(100.0, y),
+    }
+}
+
+pub(super) fn settings_row(index: usize) -> Slot {
+    let y = 180.0 + index as f32 * 32.0;
+    Slot {
+        hit: Rect::new(90.0, y - 24.0, 820.0, 30.0),
+        text: vec2(100.0, y),
+    }
+}
+
+pub(super) fn pause_panel() -> Rect {
+    Rect::new(300.0, 200.0, 400.0, 300.0)
+}
+
+pub(super) fn pause_item(index: usize) -> Slot {
+    let y = 310.0 + index as f32 * 48.0;
+    Slot {
+        hit: Rect::new(330.0, y - 34.0, 340.0, 44.0),
+        text: vec2(350.0, y),
+    }
+}
+
+pub(super) fn message_bar() -> Rect {
+    Rect::new(40.0, DESIGN_HEIGHT - 80.0, DESIGN_WIDTH - 80.0, 42.0)
+}
+
+pub(super) fn dpad_button(direction: Direction) -> Rect {
+    let offset = DPAD_BUTTON_SIZE + 4.0;
+    let (dx, dy) = match direction {
+        Direction::Up => (0.0, -offset),
+        Direction::Down => (0.0, offset),
+        Direction::Left => (-offset, 0.0),
+        Direction::Right => (offset, 0.0),
+    };
+    Rect::new(
+        DPAD_CENTER.x + dx - DPAD_BUTTON_SIZE / 2.0,
+        DPAD_CENTER.y + dy - DPAD_BUTTON_SIZE / 2.0,
+        DPAD_BUTTON_SIZE,
+        DPAD_BUTTON_SIZE,
+    )
+}
+
+/// Fits the board into the play area, capping cells at their classic size and
+/// centering it horizontally under the HUD.
+pub(super) fn board(board: Board) -> BoardLayout {
+    let columns = board.width.max(1);
+    let rows = board.height.max(1);
+    let cell = (BOARD_AREA.w / columns as f32)
+        .min(BOARD_AREA.h / rows as f32)
+        .min(MAX_CELL_SIZE);
+    let width = columns as f32 * cell;
+    BoardLayout {
+        origin: vec2((DESIGN_WIDTH - width) / 2.0, BOARD_AREA.y),
+        cell,
+        columns,
+        rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn design_sized_window_maps_one_to_one() {
+        let layout = Layout::for_screen(DESIGN_WIDTH, DESIGN_HEIGHT);
+        let probe = vec2(123.0, 456.0);
+        assert_eq!(layout.to_design(probe), probe);
+        assert_eq!(layout.rect(pause_panel()), pause_panel());
+    }
+
+    #[test]
+    fn larger_window_scales_and_letterboxes_the_canvas() {
+        let layout = Layout::for_screen(3000.0, 1520.0);
+        let item = main_menu_item(1);
+        let drawn = layout.rect(item.hit);
+
+        assert_eq!(drawn.w, item.hit.w * 2.0);
+        assert_eq!(drawn.x, 500.0 + item.hit.x * 2.0);
+        assert!(item.hit.contains(layout.to_design(drawn.center())));
+    }
+
+    #[test]
+    fn default_board_keeps_classic_cell_size() {
+        let layout = board(Board {
+            width: 12,
+            height: 12,
+        });
+        assert_eq!(layout.cell, MAX_CELL_SIZE);
+        assert_eq!(layout.origin, vec2(308.0, 130.0));
+        assert_eq!(
+            layout.cell_at(vec2(484.0, 306.0)),
+            Some(Point { x: 5, y: 5 })
+        );
+    }
+
+    #[test]
+    fn large_boards_shrink_cells_to_fit_the_play_area() {
+        let layout = board(Board {
+            width: 40,
+            height: 30,
+        });
+        let frame = layout.frame();
+        assert!(frame.h <= BOARD_AREA.h);
+        assert!(frame.w <= BOARD_AREA.w);
+        assert_eq!(layout.cell_at(frame.point() - vec2(1.0, 1.0)), None);
+    }
+}
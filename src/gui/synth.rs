@@ -0,0 +1,246 @@
+//! Tiny synthesizer that renders every sound effect at startup, so the crate
+//! ships no audio assets. Output is plain samples and WAV bytes, testable
+//! without a sound device.
+
+use super::audio::SoundEvent;
+use super::lcg_next;
+
+pub(super) const SAMPLE_RATE: u32 = 22_050;
+/// Number of pitch variants rendered for length-dependent cues.
+pub(super) const PITCH_STEPS: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Waveform {
+    Square { duty: f32 },
+    Triangle,
+    Noise,
+}
+
+/// Linear attack/decay/sustain/release, in seconds; `sustain` is a level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Envelope {
+    pub attack: f32,
+    pub decay: f32,
+    pub sustain: f32,
+    pub release: f32,
+}
+
+impl Envelope {
+    const BLIP: Envelope = Envelope {
+        attack: 0.005,
+        decay: 0.04,
+        sustain: 0.5,
+        release: 0.05,
+    };
+
+    fn level(&self, t: f32, duration: f32) -> f32 {
+        // Short clips shrink the release so every tone still fades to silence.
+        let release = self.release.min(duration);
+        let release_start = duration - release;
+        let held = if t < self.attack {
+            t / self.attack
+        } else if t < self.attack + self.decay {
+            1.0 - (1.0 - self.sustain) * (t - self.attack) / self.decay
+        } else {
+            self.sustain
+        };
+        if t >= release_start && release > 0.0 {
+            held * (1.0 - (t - release_start) / release).max(0.0)
+        } else {
+            held
+        }
+    }
+}
+
+/// One note; frequency slides linearly from `start_hz` to `end_hz`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Tone {
+    pub waveform: Waveform,
+    pub start_hz: f32,
+    pub end_hz: f32,
+    pub duration: f32,
+    pub envelope: Envelope,
+    pub gain: f32,
+}
+
+impl Tone {
+    fn blip(waveform: Waveform, start_hz: f32, end_hz: f32, duration: f32) -> Self {
+        Self {
+            waveform,
+            start_hz,
+            end_hz,
+            duration,
+            envelope: Envelope::BLIP,
+            gain: 0.5,
+        }
+    }
+}
+
+/// Renders tones back to back into mono samples in `-1.0..=1.0`.
+pub(super) fn render(tones: &[Tone]) -> Vec<f32> {
+    let mut samples = Vec::new();
+    let mut noise_seed = 0x5eed_u64;
+    let mut noise_value = 0.0;
+    for tone in tones {
+        let count = (tone.duration * SAMPLE_RATE as f32) as usize;
+        let mut phase = 0.0_f32;
+        for i in 0..count {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let hz = tone.start_hz + (tone.end_hz - tone.start_hz) * t / tone.duration;
+            let previous_phase = phase;
+            phase = (phase + hz / SAMPLE_RATE as f32).fract();
+            let raw = match tone.waveform {
+                Waveform::Square { duty } => {
+                    if phase < duty {
+                        1.0
+                    } else {
+                        -1.0
+                    }
+                }
+                Waveform::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+                Waveform::Noise => {
+                    // Sample-and-hold once per cycle so `hz` still colors the noise.
+                    if phase < previous_phase || i == 0 {
+                        noise_seed = lcg_next(noise_seed);
+                        noise_value = (noise_seed >> 40) as f32 / (1u64 << 23) as f32 - 1.0;
+                    }
+                    noise_value
+                }
+            };
+            let level = tone.envelope.level(t, tone.duration) * tone.gain;
+            samples.push((raw * level).clamp(-1.0, 1.0));
+        }
+    }
+    samples
+}
+
+/// Encodes samples as a 16-bit mono PCM WAV file.
+pub(super) fn encode_wav(samples: &[f32]) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+/// Whether an event's pitch follows the snake's length.
+pub(super) fn is_pitched(event: SoundEvent) -> bool {
+    matches!(event, SoundEvent::Eat | SoundEvent::Turn)
+}
+
+/// One semitone per two segments grown beyond the starting three.
+pub(super) fn pitch_step_for_length(length: usize) -> usize {
+    (length.saturating_sub(3) / 2).min(PITCH_STEPS - 1)
+}
+
+/// The tones for `event`, transposed up by `pitch_step` semitones.
+pub(super) fn patch(event: SoundEvent, pitch_step: usize) -> Vec<Tone> {
+    let pitch = 2f32.powf(pitch_step as f32 / 12.0);
+    let square = Waveform::Square { duty: 0.5 };
+    let narrow = Waveform::Square { duty: 0.25 };
+    match event {
+        SoundEvent::Turn => vec![Tone {
+            gain: 0.25,
+            ..Tone::blip(narrow, 440.0 * pitch, 440.0 * pitch, 0.03)
+        }],
+        SoundEvent::Eat => vec![
+            Tone::blip(square, 523.0 * pitch, 523.0 * pitch, 0.05),
+            Tone::blip(square, 784.0 * pitch, 880.0 * pitch, 0.08),
+        ],
+        SoundEvent::Collision => vec![Tone {
+            envelope: Envelope {
+                attack: 0.002,
+                decay: 0.1,
+                sustain: 0.3,
+                release: 0.15,
+            },
+            gain: 0.6,
+            ..Tone::blip(Waveform::Noise, 2_000.0, 200.0, 0.35)
+        }],
+        SoundEvent::Reposition => vec![
+            Tone::blip(Waveform::Triangle, 880.0, 220.0, 0.12),
+            Tone::blip(Waveform::Triangle, 330.0, 660.0, 0.12),
+        ],
+        SoundEvent::Unlock => [523.0, 659.0, 784.0, 1_047.0]
+            .into_iter()
+            .map(|hz| Tone::blip(square, hz, hz, 0.09))
+            .collect(),
+        SoundEvent::MenuMove => vec![Tone {
+            gain: 0.2,
+            ..Tone::blip(Waveform::Triangle, 660.0, 660.0, 0.03)
+        }],
+        SoundEvent::MenuConfirm => vec![
+            Tone::blip(Waveform::Triangle, 660.0, 660.0, 0.04),
+            Tone::blip(Waveform::Triangle, 990.0, 990.0, 0.06),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zero_crossings(samples: &[f32]) -> usize {
+        samples
+            .windows(2)
+            .filter(|pair| (pair[0] < 0.0) != (pair[1] < 0.0))
+            .count()
+    }
+
+    #[test]
+    fn every_event_renders_a_short_bounded_clip() {
+        for event in SoundEvent::ALL {
+            let samples = render(&patch(event, 0));
+            assert!(!samples.is_empty(), "{event:?}");
+            assert!(samples.len() < SAMPLE_RATE as usize, "{event:?}");
+            assert!(samples.iter().all(|s| s.abs() <= 1.0), "{event:?}");
+            assert!(
+                samples.last().unwrap().abs() < 0.05,
+                "{event:?} ends with a click"
+            );
+        }
+    }
+
+    #[test]
+    fn longer_snakes_raise_the_eat_pitch() {
+        let low = render(&patch(SoundEvent::Eat, pitch_step_for_length(3)));
+        let high = render(&patch(SoundEvent::Eat, pitch_step_for_length(40)));
+        assert_eq!(pitch_step_for_length(40), PITCH_STEPS - 1);
+        assert!(zero_crossings(&high) > zero_crossings(&low) * 3 / 2);
+    }
+
+    #[test]
+    fn noise_is_deterministic() {
+        let tones = patch(SoundEvent::Collision, 0);
+        assert_eq!(render(&tones), render(&tones));
+    }
+
+    #[test]
+    fn wav_header_describes_sixteen_bit_mono_pcm() {
+        let bytes = encode_wav(&[0.0, 1.0, -1.0]);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()), 42);
+        assert_eq!(&bytes[8..16], b"WAVEfmt ");
+        assert_eq!(u16::from_le_bytes([bytes[22], bytes[23]]), 1);
+        assert_eq!(
+            u32::from_le_bytes(bytes[24..28].try_into().unwrap()),
+            SAMPLE_RATE
+        );
+        assert_eq!(u32::from_le_bytes(bytes[40..44].try_into().unwrap()), 6);
+        assert_eq!(i16::from_le_bytes([bytes[46], bytes[47]]), i16::MAX);
+    }
+}
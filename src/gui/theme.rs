@@ -0,0 +1,172 @@
+//! Built-in color palettes. The active palette is chosen by the profile's
+//! [`ThemeId`]; drawing code reads colors from [`Theme`] instead of literals.
+
+use macroquad::prelude::*;
+
+use crate::{FoodKind, ThemeId};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Theme {
+    pub background: Color,
+    pub snake_head: Color,
+    pub snake_body: Color,
+    pub food: Color,
+    pub walls: Color,
+    pub text: Color,
+    pub text_muted: Color,
+    pub text_hint: Color,
+    pub accent: Color,
+    pub highlight: Color,
+    pub inactive: Color,
+    pub danger: Color,
+    pub ghost: Color,
+    pub panel: Color,
+    pub overlay: Color,
+    pub message_background: Color,
+    pub message_text: Color,
+}
+
+const CLASSIC: Theme = Theme {
+    background: Color::from_rgba(13, 20, 26, 255),
+    snake_head: Color::from_rgba(127, 255, 90, 255),
+    snake_body: Color::from_rgba(89, 196, 64, 255),
+    food: Color::from_rgba(255, 90, 79, 255),
+    walls: GRAY,
+    text: WHITE,
+    text_muted: LIGHTGRAY,
+    text_hint: GRAY,
+    accent: Color::from_rgba(95, 242, 153, 255),
+    highlight: YELLOW,
+    inactive: DARKGRAY,
+    danger: Color::from_rgba(255, 119, 119, 255),
+    ghost: Color::from_rgba(255, 255, 255, 38),
+    panel: Color::from_rgba(22, 32, 40, 240),
+    overlay: Color::from_rgba(0, 0, 0, 170),
+    message_background: Color::from_rgba(35, 12, 12, 230),
+    message_text: Color::from_rgba(255, 135, 135, 255),
+};
+
+// Okabe-Ito hues: the snake stays on the blue axis and food on orange, which
+// remain distinct without red/green discrimination.
+const DEUTERANOPIA: Theme = Theme {
+    background: Color::from_rgba(12, 16, 24, 255),
+    snake_head: Color::from_rgba(86, 180, 233, 255),
+    snake_body: Color::from_rgba(0, 114, 178, 255),
+    food: Color::from_rgba(230, 159, 0, 255),
+    walls: Color::from_rgba(150, 150, 150, 255),
+    text: WHITE,
+    text_muted: Color::from_rgba(210, 210, 210, 255),
+    text_hint: Color::from_rgba(150, 150, 150, 255),
+    accent: Color::from_rgba(86, 180, 233, 255),
+    highlight: Color::from_rgba(240, 228, 66, 255),
+    inactive: Color::from_rgba(80, 80, 80, 255),
+    danger: Color::from_rgba(213, 94, 0, 255),
+    ghost: Color::from_rgba(255, 255, 255, 38),
+    panel: Color::from_rgba(20, 28, 40, 240),
+    overlay: Color::from_rgba(0, 0, 0, 170),
+    message_background: Color::from_rgba(40, 24, 6, 230),
+    message_text: Color::from_rgba(230, 159, 0, 255),
+};
+
+// Protanopes lose long-wavelength reds, so food moves to yellow and warnings
+// to orange while the snake keeps the blue axis.
+const PROTANOPIA: Theme = Theme {
+    background: Color::from_rgba(12, 16, 24, 255),
+    snake_head: Color::from_rgba(200, 230, 255, 255),
+    snake_body: Color::from_rgba(0, 114, 178, 255),
+    food: Color::from_rgba(240, 228, 66, 255),
+    walls: Color::from_rgba(150, 150, 150, 255),
+    text: WHITE,
+    text_muted: Color::from_rgba(210, 210, 210, 255),
+    text_hint: Color::from_rgba(150, 150, 150, 255),
+    accent: Color::from_rgba(86, 180, 233, 255),
+    highlight: Color::from_rgba(240, 228, 66, 255),
+    inactive: Color::from_rgba(80, 80, 80, 255),
+    danger: Color::from_rgba(230, 159, 0, 255),
+    ghost: Color::from_rgba(255, 255, 255, 38),
+    panel: Color::from_rgba(20, 28, 40, 240),
+    overlay: Color::from_rgba(0, 0, 0, 170),
+    message_background: Color::from_rgba(40, 34, 6, 230),
+    message_text: Color::from_rgba(240, 228, 66, 255),
+};
+
+const HIGH_CONTRAST: Theme = Theme {
+    background: BLACK,
+    snake_head: WHITE,
+    snake_body: Color::from_rgba(0, 255, 255, 255),
+    food: Color::from_rgba(255, 255, 0, 255),
+    walls: WHITE,
+    text: WHITE,
+    text_muted: WHITE,
+    text_hint: Color::from_rgba(220, 220, 220, 255),
+    accent: Color::from_rgba(0, 255, 255, 255),
+    highlight: Color::from_rgba(255, 255, 0, 255),
+    inactive: Color::from_rgba(160, 160, 160, 255),
+    danger: Color::from_rgba(255, 96, 96, 255),
+    ghost: Color::from_rgba(255, 255, 255, 64),
+    panel: BLACK,
+    overlay: Color::from_rgba(0, 0, 0, 210),
+    message_background: BLACK,
+    message_text: Color::from_rgba(255, 255, 0, 255),
+};
+
+impl Theme {
+    pub fn for_id(id: ThemeId) -> Self {
+        match id {
+            ThemeId::Classic => CLASSIC,
+            ThemeId::Deuteranopia => DEUTERANOPIA,
+            ThemeId::Protanopia => PROTANOPIA,
+            ThemeId::HighContrast => HIGH_CONTRAST,
+        }
+    }
+
+    /// Special foods borrow UI roles; their shapes keep them apart when two
+    /// roles share a hue.
+    pub fn food_color(&self, kind: FoodKind) -> Color {
+        match kind {
+            FoodKind::Normal => self.food,
+            FoodKind::Golden => self.highlight,
+            FoodKind::Shrinking => self.danger,
+            FoodKind::SpeedUp => self.accent,
+            FoodKind::Bonus => self.text,
+        }
+    }
+}
+
+pub(super) fn theme_label(id: ThemeId) -> &'static str {
+    match id {
+        ThemeId::Classic => "classic",
+        ThemeId::Deuteranopia => "deuteranopia-safe",
+        ThemeId::Protanopia => "protanopia-safe",
+        ThemeId::HighContrast => "high-contrast",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn luminance(color: Color) -> f32 {
+        0.2126 * color.r + 0.7152 * color.g + 0.0722 * color.b
+    }
+
+    #[test]
+    fn every_theme_keeps_gameplay_colors_distinguishable() {
+        for id in ThemeId::ALL {
+            let theme = Theme::for_id(id);
+            assert_ne!(theme.food, theme.snake_head, "{id:?}");
+            assert_ne!(theme.food, theme.snake_body, "{id:?}");
+            assert!(
+                luminance(theme.text) - luminance(theme.background) > 0.7,
+                "{id:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn high_contrast_uses_pure_black_background() {
+        let theme = Theme::for_id(ThemeId::HighContrast);
+        assert_eq!(theme.background, BLACK);
+        assert_eq!(theme.text, WHITE);
+    }
+}
@@ -0,0 +1,715 @@
+//! Leaderboard export and import as CSV or JSON. Both formats carry the same
+//! columns, one row per run; imports are de-duplicated by run id and checked
+//! against the mode's rules before they are merged.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    Board, CustomPolicy, CustomRules, Difficulty, FoodKind, GameMode, LeaderboardEntry,
+    Leaderboards, MAX_WEIGHT, ModePolicy, ModeRegistry, PUZZLES, RunMetrics, SPEED_OPTIONS, STAGES,
+    ScoreWeights, ToolRegistry, effects_from_tools, validate_profile_name,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+pub const COLUMNS: [&str; 14] = [
+    "run_id",
+    "player",
+    "mode",
+    "score",
+    "survival_ticks",
+    "food_eaten",
+    "growth_units",
+    "loadout",
+    "preset",
+    "perfect",
+    "difficulty",
+    "board_width",
+    "board_height",
+    "recorded_at",
+];
+
+/// Columns written as JSON numbers; the rest are strings apart from
+/// `perfect` (a boolean) and `preset` (a string or null).
+const NUMERIC_COLUMNS: [&str; 8] = [
+    "run_id",
+    "score",
+    "survival_ticks",
+    "food_eaten",
+    "growth_units",
+    "board_width",
+    "board_height",
+    "recorded_at",
+];
+
+/// One row keyed by column name, with every value as text.
+type Record = BTreeMap<String, String>;
+
+/// Every row of `mode`, in rank order.
+pub fn export(boards: &Leaderboards, mode: GameMode, format: ExportFormat) -> String {
+    let rows: Vec<[String; 14]> = boards.rows(mode).iter().map(fields).collect();
+    match format {
+        ExportFormat::Csv => {
+            let mut out = COLUMNS.join(",");
+            out.push('\n');
+            for row in rows {
+                let cells: Vec<String> = row.iter().map(|cell| csv_quote(cell)).collect();
+                out.push_str(&cells.join(","));
+                out.push('\n');
+            }
+            out
+        }
+        ExportFormat::Json => {
+            let objects: Vec<String> = rows
+                .iter()
+                .map(|row| {
+                    let pairs: Vec<String> = COLUMNS
+                        .iter()
+                        .zip(row)
+                        .map(|(column, value)| {
+                            format!("\"{column}\": {}", json_value(column, value))
+                        })
+                        .collect();
+                    format!("  {{{}}}", pairs.join(", "))
+                })
+                .collect();
+            if objects.is_empty() {
+                "[]\n".to_string()
+            } else {
+                format!("[\n{}\n]\n", objects.join(",\n"))
+            }
+        }
+    }
+}
+
+/// Parses `text`. A malformed file is an error; each row is then converted
+/// on its own, so one bad row doesn't sink the rest.
+pub fn read_entries(
+    text: &str,
+    format: ExportFormat,
+    modes: &ModeRegistry,
+) -> Result<Vec<Result<LeaderboardEntry, String>>, String> {
+    let records = match format {
+        ExportFormat::Csv => csv_records(text)?,
+        ExportFormat::Json => json_records(text)?,
+    };
+    Ok(records
+        .iter()
+        .map(|record| entry_from_record(record, modes))
+        .collect())
+}
+
+/// Checks an imported entry could have come from a run of its mode: known
+/// tools, a full loadout where the mode needs one, a preset only on custom
+/// runs, and a score no lower than the mode's scoring gives for the
+/// recorded food and survival before any bonus.
+pub fn check_entry(entry: &LeaderboardEntry, registry: &ToolRegistry) -> Result<(), String> {
+    validate_profile_name(&entry.player)?;
+    let tools: Vec<&str> = match entry.loadout_summary.as_str() {
+        "none" => Vec::new(),
+        summary => summary.split('+').collect(),
+    };
+    if let Some(unknown) = tools.iter().find(|tool| registry.tool(tool).is_none()) {
+        return Err(format!("unknown tool {unknown}"));
+    }
+    if !entry.mode.requires_loadout() && entry.mode != GameMode::CUSTOM && !tools.is_empty() {
+        return Err(format!("{} runs don't take a loadout", entry.mode.id()));
+    }
+    if entry.mode.requires_loadout() && tools.len() != 3 {
+        return Err(format!(
+            "{} runs need a three-tool loadout",
+            entry.mode.id()
+        ));
+    }
+    if entry.preset.is_some() != (entry.mode == GameMode::CUSTOM) {
+        return Err("only custom runs carry a preset".to_string());
+    }
+    if entry.mode == GameMode::PUZZLE {
+        if !puzzle_scores(entry).any(|score| score == entry.score) {
+            return Err(format!(
+                "score {} isn't one a puzzle on a {}x{} board can record",
+                entry.score, entry.board.width, entry.board.height
+            ));
+        }
+        return Ok(());
+    }
+    let (min, max) = score_range(entry, &tools);
+    if entry.score < min {
+        return Err(format!(
+            "score {} is below the {min} a {} run earns for its food and survival",
+            entry.score,
+            entry.mode.id()
+        ));
+    }
+    if entry.score > max {
+        return Err(format!(
+            "score {} is above the {max} a {} run can earn for its food and survival",
+            entry.score,
+            entry.mode.id()
+        ));
+    }
+    Ok(())
+}
+
+/// Every score a puzzle run on `entry`'s board can record: ten per food when
+/// unsolved, or stars and spare moves for a solve in any number of moves.
+fn puzzle_scores(entry: &LeaderboardEntry) -> impl Iterator<Item = u64> + '_ {
+    let solved = PUZZLES
+        .iter()
+        .filter(|puzzle| puzzle.board == entry.board)
+        .flat_map(|puzzle| {
+            (1..=puzzle.move_budget).map(|moves| {
+                puzzle.stars(moves) as u64 * 100 + (puzzle.move_budget - moves) as u64 * 10
+            })
+        });
+    std::iter::once(entry.food_eaten.saturating_mul(10)).chain(solved)
+}
+
+/// Custom scoring with every weight set to `weight`.
+fn custom_policy(weight: u32) -> Box<dyn ModePolicy> {
+    Box::new(CustomPolicy {
+        rules: CustomRules {
+            weights: ScoreWeights {
+                per_food: weight,
+                per_ten_ticks: weight,
+                per_speed_point: weight,
+            },
+            ..CustomRules::default()
+        },
+    })
+}
+
+/// Lowest and highest score a run of `entry`'s mode can record with its food,
+/// growth, survival, board and loadout. Food kinds, time at raised speed and
+/// hazards aren't exported, so the range spans none of their bonuses to the
+/// most the run could have collected. Difficulty sets pace and food density
+/// but never scoring, so it doesn't move the range. Custom weights stay with
+/// the preset, so custom runs span the lightest to the heaviest weights.
+fn score_range(entry: &LeaderboardEntry, tools: &[&str]) -> (u64, u64) {
+    let (floor, policy) = if entry.mode == GameMode::CUSTOM {
+        (custom_policy(0), custom_policy(MAX_WEIGHT))
+    } else {
+        (entry.mode.policy(), entry.mode.policy())
+    };
+    let effects = effects_from_tools(tools);
+    let base = RunMetrics {
+        food_eaten: entry.food_eaten,
+        growth_units: entry.growth_units,
+        survival_ticks: entry.survival_ticks,
+        ..RunMetrics::default()
+    };
+    let completion = policy.completion_bonus(entry.board);
+    let mut min = floor.score(&base, &effects);
+    if entry.perfect {
+        min = min.saturating_add(completion);
+    }
+
+    let best_food = FoodKind::ALL
+        .into_iter()
+        .max_by_key(|kind| policy.food_bonus(*kind))
+        .unwrap_or(FoodKind::Normal);
+    // Campaign runs take their pace from the stage and custom runs from the
+    // preset rather than the policy.
+    let stage_curves = STAGES
+        .iter()
+        .filter(|_| entry.mode == GameMode::CAMPAIGN)
+        .map(|stage| stage.speed_curve);
+    let preset_curves = SPEED_OPTIONS
+        .iter()
+        .filter(|_| entry.mode == GameMode::CUSTOM)
+        .map(|(_, curve)| *curve);
+    let top_speed = std::iter::once(policy.speed_curve())
+        .chain(stage_curves)
+        .chain(preset_curves)
+        .map(|curve| curve.level(&base) as u64)
+        .max()
+        .unwrap_or(0);
+    let top_hazards = policy
+        .arena_schedule()
+        .map_or(0, |schedule| schedule.max_level(entry.survival_ticks));
+    let ceiling = RunMetrics {
+        eaten_by_kind: BTreeMap::from([(best_food, entry.food_eaten)]),
+        speed_level_ticks: entry.survival_ticks.saturating_mul(top_speed),
+        hazard_ticks: entry.survival_ticks.saturating_mul(top_hazards),
+        ..base
+    };
+    let max = policy.score(&ceiling, &effects).saturating_add(completion);
+    (min, max)
+}
+
+fn fields(entry: &LeaderboardEntry) -> [String; 14] {
+    [
+        entry.run_id.to_string(),
+        entry.player.clone(),
+        entry.mode.id().to_string(),
+        entry.score.to_string(),
+        entry.survival_ticks.to_string(),
+        entry.food_eaten.to_string(),
+        entry.growth_units.to_string(),
+        entry.loadout_summary.clone(),
+        entry.preset.clone().unwrap_or_default(),
+        entry.perfect.to_string(),
+        difficulty_id(entry.difficulty),
+        entry.board.width.to_string(),
+        entry.board.height.to_string(),
+        entry.recorded_at.to_string(),
+    ]
+}
+
+fn difficulty_id(difficulty: Difficulty) -> String {
+    format!("{difficulty:?}").to_lowercase()
+}
+
+fn entry_from_record(record: &Record, modes: &ModeRegistry) -> Result<LeaderboardEntry, String> {
+    let field = |name: &str| {
+        record
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| format!("missing {name}"))
+    };
+    let number = |name: &str| {
+        field(name)?
+            .parse::<u64>()
+            .map_err(|_| format!("{name} is not a whole number"))
+    };
+    let side = |name: &str| match field(name)?.parse::<i32>() {
+        Ok(cells) if cells > 0 => Ok(cells),
+        _ => Err(format!("{name} is not a positive whole number")),
+    };
+    let mode_id = field("mode")?;
+    let mode = modes
+        .get(mode_id)
+        .ok_or_else(|| format!("unknown mode {mode_id}"))?;
+    let difficulty_name = field("difficulty")?;
+    let difficulty = Difficulty::ALL
+        .into_iter()
+        .find(|difficulty| difficulty_id(*difficulty) == difficulty_name)
+        .ok_or_else(|| format!("unknown difficulty {difficulty_name}"))?;
+    let perfect = match field("perfect")? {
+        "true" => true,
+        "false" => false,
+        other => return Err(format!("perfect must be true or false, not {other}")),
+    };
+    let preset = field("preset")?;
+    Ok(LeaderboardEntry {
+        mode,
+        score: number("score")?,
+        survival_ticks: number("survival_ticks")?,
+        loadout_summary: field("loadout")?.to_string(),
+        perfect,
+        difficulty,
+        preset: (!preset.is_empty()).then(|| preset.to_string()),
+        food_eaten: number("food_eaten")?,
+        growth_units: number("growth_units")?,
+        recorded_at: number("recorded_at")?,
+        board: Board {
+            width: side("board_width")?,
+            height: side("board_height")?,
+        },
+        run_id: number("run_id")?,
+        player: field("player")?.to_string(),
+    })
+}
+
+fn csv_quote(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// Splits CSV into rows of cells, honouring quoted cells.
+fn csv_rows(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if cell.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut cell)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => cell.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted cell".to_string());
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    rows.retain(|row| !(row.len() == 1 && row[0].is_empty()));
+    Ok(rows)
+}
+
+fn csv_records(text: &str) -> Result<Vec<Record>, String> {
+    let mut rows = csv_rows(text)?.into_iter();
+    let header = rows.next().ok_or("missing header row")?;
+    if let Some(missing) = COLUMNS
+        .iter()
+        .find(|column| !header.iter().any(|h| h == *column))
+    {
+        return Err(format!("missing column {missing}"));
+    }
+    rows.enumerate()
+        .map(|(index, row)| {
+            if row.len() != header.len() {
+                return Err(format!(
+                    "row {}: expected {} cells, found {}",
+                    index + 1,
+                    header.len(),
+                    row.len()
+                ));
+            }
+            Ok(header.iter().cloned().zip(row).collect())
+        })
+        .collect()
+}
+
+fn json_value(column: &str, value: &str) -> String {
+    if NUMERIC_COLUMNS.contains(&column) || column == "perfect" {
+        value.to_string()
+    } else if column == "preset" && value.is_empty() {
+        "null".to_string()
+    } else {
+        json_string(value)
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// The subset of JSON exports use: an array of flat objects whose values
+/// are strings, whole numbers, booleans or null.
+fn json_records(text: &str) -> Result<Vec<Record>, String> {
+    let mut parser = JsonParser {
+        chars: text.chars().collect(),
+        at: 0,
+    };
+    let mut records = Vec::new();
+    parser.expect('[')?;
+    if !parser.eat(']') {
+        loop {
+            records.push(parser.object()?);
+            if parser.eat(']') {
+                break;
+            }
+            parser.expect(',')?;
+        }
+    }
+    parser.skip_space();
+    if parser.at != parser.chars.len() {
+        return Err(parser.error("trailing data"));
+    }
+    Ok(records)
+}
+
+struct JsonParser {
+    chars: Vec<char>,
+    at: usize,
+}
+
+impl JsonParser {
+    fn error(&self, what: &str) -> String {
+        format!("JSON {what} at character {}", self.at)
+    }
+
+    fn skip_space(&mut self) {
+        while self.chars.get(self.at).is_some_and(|c| c.is_whitespace()) {
+            self.at += 1;
+        }
+    }
+
+    fn eat(&mut self, wanted: char) -> bool {
+        self.skip_space();
+        if self.chars.get(self.at) == Some(&wanted) {
+            self.at += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, wanted: char) -> Result<(), String> {
+        if self.eat(wanted) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{wanted}'")))
+        }
+    }
+
+    fn object(&mut self) -> Result<Record, String> {
+        let mut record = Record::new();
+        self.expect('{')?;
+        if self.eat('}') {
+            return Ok(record);
+        }
+        loop {
+            self.skip_space();
+            let key = self.string()?;
+            self.expect(':')?;
+            let value = self.scalar()?;
+            record.insert(key, value);
+            if self.eat('}') {
+                return Ok(record);
+            }
+            self.expect(',')?;
+        }
+    }
+
+    /// A string, whole number, boolean or null, as text; null is empty.
+    fn scalar(&mut self) -> Result<String, String> {
+        self.skip_space();
+        match self.chars.get(self.at) {
+            Some('"') => self.string(),
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.at;
+                while self.chars.get(self.at).is_some_and(char::is_ascii_digit) {
+                    self.at += 1;
+                }
+                Ok(self.chars[start..self.at].iter().collect())
+            }
+            _ => {
+                for word in ["true", "false", "null"] {
+                    let end = self.at + word.len();
+                    if self
+                        .chars
+                        .get(self.at..end)
+                        .is_some_and(|found| found.iter().copied().eq(word.chars()))
+                    {
+                        self.at = end;
+                        return Ok(if word == "null" {
+                            String::new()
+                        } else {
+                            word.to_string()
+                        });
+                    }
+                }
+                Err(self.error("unsupported value"))
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        if self.chars.get(self.at) != Some(&'"') {
+            return Err(self.error("expected a string"));
+        }
+        self.at += 1;
+        let mut out = String::new();
+        loop {
+            let c = *self
+                .chars
+                .get(self.at)
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.at += 1;
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let escape = *self
+                        .chars
+                        .get(self.at)
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.at += 1;
+                    match escape {
+                        '"' | '\\' | '/' => out.push(escape),
+                        'n' => out.push('\n'),
+                        'r' => out.push('\r'),
+                        't' => out.push('\t'),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'u' => {
+                            let hex: String = self
+                                .chars
+                                .get(self.at..self.at + 4)
+                                .ok_or_else(|| self.error("short \\u escape"))?
+                                .iter()
+                                .collect();
+                            self.at += 4;
+                            let decoded = u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("bad \\u escape"))?;
+                            out.push(decoded);
+                        }
+                        _ => return Err(self.error("bad escape")),
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(run_id: u64, preset: Option<&str>) -> LeaderboardEntry {
+        LeaderboardEntry {
+            mode: GameMode::CUSTOM,
+            score: 120,
+            survival_ticks: 300,
+            loadout_summary: "soft-wrap".to_string(),
+            perfect: false,
+            difficulty: Difficulty::Hard,
+            preset: preset.map(str::to_string),
+            food_eaten: 9,
+            growth_units: 11,
+            recorded_at: 1_700_000_000,
+            board: Board {
+                width: 14,
+                height: 10,
+            },
+            run_id,
+            player: "Ana".to_string(),
+        }
+    }
+
+    #[test]
+    fn both_formats_round_trip_awkward_text() {
+        let mut boards = Leaderboards::default();
+        boards.submit(entry(7, Some("Wide, \"open\" \\ walls")));
+        boards.submit(entry(8, Some("Plain")));
+        let modes = ModeRegistry::builtin();
+        for format in [ExportFormat::Csv, ExportFormat::Json] {
+            let text = export(&boards, GameMode::CUSTOM, format);
+            let read: Vec<LeaderboardEntry> = read_entries(&text, format, &modes)
+                .unwrap()
+                .into_iter()
+                .map(Result::unwrap)
+                .collect();
+            assert_eq!(read, boards.rows(GameMode::CUSTOM), "{format:?}");
+        }
+        assert_eq!(export(&boards, GameMode::ARENA, ExportFormat::Json), "[]\n");
+    }
+
+    #[test]
+    fn malformed_files_fail_and_bad_rows_are_reported_alone() {
+        let modes = ModeRegistry::builtin();
+        assert!(read_entries("[{\"run_id\": -1}]", ExportFormat::Json, &modes).is_err());
+        assert!(read_entries("run_id,player\n\"1", ExportFormat::Csv, &modes).is_err());
+        assert!(read_entries("run_id\n1\n", ExportFormat::Csv, &modes).is_err());
+
+        let mut text = export(
+            &Leaderboards::default(),
+            GameMode::CUSTOM,
+            ExportFormat::Csv,
+        );
+        text.push_str("1,Ana,custom,5,10,1,1,none,P,false,hard,12,12,0\n");
+        text.push_str("2,Ana,zen,5,10,1,1,none,,false,hard,12,12,0\n");
+        let rows = read_entries(&text, ExportFormat::Csv, &modes).unwrap();
+        assert!(rows[0].is_ok());
+        assert_eq!(rows[1].as_ref().unwrap_err(), "unknown mode zen");
+    }
+
+    #[test]
+    fn entries_are_checked_against_their_mode() {
+        let registry = ToolRegistry::demo();
+        assert!(check_entry(&entry(1, Some("P")), &registry).is_ok());
+        assert!(check_entry(&entry(1, None), &registry).is_err());
+        let mut custom = entry(1, Some("P"));
+        custom.score = 999_999_999;
+        assert!(
+            check_entry(&custom, &registry)
+                .unwrap_err()
+                .contains("above")
+        );
+
+        let mut puzzle = entry(1, None);
+        puzzle.mode = GameMode::PUZZLE;
+        puzzle.loadout_summary = "none".to_string();
+        puzzle.board = PUZZLES[0].board;
+        puzzle.food_eaten = 2;
+        for score in [20, 2 * 100 + 3 * 10] {
+            puzzle.score = score;
+            assert!(check_entry(&puzzle, &registry).is_ok());
+        }
+        puzzle.score = 25;
+        assert!(
+            check_entry(&puzzle, &registry)
+                .unwrap_err()
+                .contains("isn't one a puzzle")
+        );
+
+        let mut challenge = entry(1, None);
+        challenge.mode = GameMode::CHALLENGE;
+        challenge.loadout_summary = "none".to_string();
+        assert!(
+            check_entry(&challenge, &registry)
+                .unwrap_err()
+                .contains("below")
+        );
+        challenge.score = 300_090;
+        assert!(check_entry(&challenge, &registry).is_ok());
+        challenge.score = 999_999_999;
+        challenge.food_eaten = 1;
+        assert!(
+            check_entry(&challenge, &registry)
+                .unwrap_err()
+                .contains("above")
+        );
+
+        let mut practice = entry(1, None);
+        practice.mode = GameMode::PRACTICE;
+        practice.loadout_summary = "none".to_string();
+        assert!(check_entry(&practice, &registry).is_ok());
+        practice.loadout_summary = "soft-wrap+rewind-step+turn-buffer".to_string();
+        assert_eq!(
+            check_entry(&practice, &registry).unwrap_err(),
+            "practice runs don't take a loadout"
+        );
+
+        let mut experimental = entry(1, None);
+        experimental.mode = GameMode::EXPERIMENTAL;
+        experimental.score = 200;
+        assert!(
+            check_entry(&experimental, &registry)
+                .unwrap_err()
+                .contains("three-tool")
+        );
+        experimental.loadout_summary = "soft-wrap+warp-drive+turn-buffer".to_string();
+        assert_eq!(
+            check_entry(&experimental, &registry).unwrap_err(),
+            "unknown tool warp-drive"
+        );
+    }
+}
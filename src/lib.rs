@@ -1,18 +1,32 @@
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
 
+pub mod arena;
+pub mod campaign;
+pub mod custom;
+pub mod food;
 pub mod gui;
-
-pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+pub mod leaderboard_io;
+pub mod modes;
+pub mod puzzle;
+
+pub use arena::{ARENA_SCHEDULE, Arena, ArenaSchedule, Roamer};
+pub use campaign::{STAGES, Stage, StageGoal};
+pub use custom::{CustomRules, FOOD_OPTIONS, MAX_WEIGHT, SPEED_OPTIONS, ScoreWeights};
+pub use food::{Food, FoodKind, FoodPattern, FoodPlacement, FoodRules};
+pub use leaderboard_io::ExportFormat;
+pub use modes::{GameMode, ModeDefinition, ModeRegistry};
+pub use puzzle::{ActivePuzzle, PUZZLES, Puzzle, PuzzleGoal};
+
+pub const CURRENT_SCHEMA_VERSION: u32 = 3;
+pub const DEFAULT_PROFILE_NAME: &str = "Player";
+pub const MAX_PROFILE_NAME_LEN: usize = 16;
+/// Profiles one engine keeps, the active one included.
+pub const MAX_PROFILES: usize = 8;
 pub const DEFAULT_THRESHOLDS: [u64; 4] = [15, 40, 80, 140];
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub enum GameMode {
-    Practice,
-    Challenge,
-    Experimental,
-    Invincible,
-}
+/// Extra simulation speed per speed level; the slow-window tool halves it.
+pub const SPEED_STEP_PERCENT: u32 = 8;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CollisionOutcome {
@@ -146,26 +160,22 @@ pub struct Point {
 }
 
 impl Point {
-    fn within(self, board: Board) -> bool {
+    pub(crate) fn within(self, board: Board) -> bool {
         self.x >= 0 && self.y >= 0 && self.x < board.width && self.y < board.height
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct RunMetrics {
     pub food_eaten: u64,
     pub growth_units: u64,
     pub survival_ticks: u64,
-}
-
-impl Default for RunMetrics {
-    fn default() -> Self {
-        Self {
-            food_eaten: 0,
-            growth_units: 0,
-            survival_ticks: 0,
-        }
-    }
+    pub repositions: u64,
+    pub eaten_by_kind: BTreeMap<FoodKind, u64>,
+    /// Speed level summed over every tick survived.
+    pub speed_level_ticks: u64,
+    /// Arena hazard level summed over every tick survived.
+    pub hazard_ticks: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -174,6 +184,169 @@ pub struct LeaderboardEntry {
     pub score: u64,
     pub survival_ticks: u64,
     pub loadout_summary: String,
+    /// The run filled the board without ever being repositioned.
+    pub perfect: bool,
+    pub difficulty: Difficulty,
+    /// Name of the custom preset the run was played with.
+    pub preset: Option<String>,
+    pub food_eaten: u64,
+    pub growth_units: u64,
+    /// When the run finished, in seconds since the Unix epoch.
+    pub recorded_at: u64,
+    pub board: Board,
+    /// Identifies the run across boards.
+    pub run_id: u64,
+    /// Name of the profile that played the run.
+    pub player: String,
+}
+
+/// Narrows a mode's leaderboard. `None` fields match every row.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LeaderboardFilter {
+    pub difficulty: Option<Difficulty>,
+    pub loadout: Option<String>,
+    pub board: Option<Board>,
+    /// Inclusive bounds on `recorded_at`.
+    pub recorded_from: Option<u64>,
+    pub recorded_until: Option<u64>,
+}
+
+impl LeaderboardFilter {
+    pub fn matches(&self, entry: &LeaderboardEntry) -> bool {
+        self.difficulty
+            .is_none_or(|wanted| entry.difficulty == wanted)
+            && self
+                .loadout
+                .as_ref()
+                .is_none_or(|wanted| entry.loadout_summary == *wanted)
+            && self.board.is_none_or(|wanted| entry.board == wanted)
+            && self
+                .recorded_from
+                .is_none_or(|from| entry.recorded_at >= from)
+            && self
+                .recorded_until
+                .is_none_or(|until| entry.recorded_at <= until)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankField {
+    Score,
+    Survival,
+    Food,
+    Growth,
+    Date,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankOrder {
+    Highest,
+    Lowest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RankKey {
+    pub field: RankField,
+    pub order: RankOrder,
+}
+
+impl RankKey {
+    pub const fn highest(field: RankField) -> Self {
+        Self {
+            field,
+            order: RankOrder::Highest,
+        }
+    }
+
+    pub const fn lowest(field: RankField) -> Self {
+        Self {
+            field,
+            order: RankOrder::Lowest,
+        }
+    }
+
+    /// `Less` when `a` ranks above `b` on this key.
+    pub fn compare(self, a: &LeaderboardEntry, b: &LeaderboardEntry) -> Ordering {
+        let value = |entry: &LeaderboardEntry| match self.field {
+            RankField::Score => entry.score,
+            RankField::Survival => entry.survival_ticks,
+            RankField::Food => entry.food_eaten,
+            RankField::Growth => entry.growth_units,
+            RankField::Date => entry.recorded_at,
+        };
+        match self.order {
+            RankOrder::Highest => value(b).cmp(&value(a)),
+            RankOrder::Lowest => value(a).cmp(&value(b)),
+        }
+    }
+}
+
+/// Up to three ranking keys, applied in order. Entries still tied rank the
+/// earlier run first, then in submission order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ranking {
+    pub primary: RankKey,
+    pub secondary: Option<RankKey>,
+    pub tertiary: Option<RankKey>,
+}
+
+impl Ranking {
+    /// Starts a ranking on `primary`; finish it with [`RankingBuilder::build`].
+    pub const fn by(primary: RankKey) -> RankingBuilder<1> {
+        RankingBuilder(Self {
+            primary,
+            secondary: None,
+            tertiary: None,
+        })
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = RankKey> {
+        std::iter::once(self.primary)
+            .chain(self.secondary)
+            .chain(self.tertiary)
+    }
+
+    pub fn compare(&self, a: &LeaderboardEntry, b: &LeaderboardEntry) -> Ordering {
+        self.keys()
+            .fold(Ordering::Equal, |order, key| {
+                order.then_with(|| key.compare(a, b))
+            })
+            .then_with(|| a.recorded_at.cmp(&b.recorded_at))
+    }
+}
+
+/// A [`Ranking`] under construction holding `KEYS` keys. `then` only exists
+/// while there is room, so a fourth key doesn't compile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RankingBuilder<const KEYS: usize>(Ranking);
+
+impl RankingBuilder<1> {
+    pub const fn then(mut self, key: RankKey) -> RankingBuilder<2> {
+        self.0.secondary = Some(key);
+        RankingBuilder(self.0)
+    }
+}
+
+impl RankingBuilder<2> {
+    pub const fn then(mut self, key: RankKey) -> RankingBuilder<3> {
+        self.0.tertiary = Some(key);
+        RankingBuilder(self.0)
+    }
+}
+
+impl<const KEYS: usize> RankingBuilder<KEYS> {
+    pub const fn build(self) -> Ranking {
+        self.0
+    }
+}
+
+/// What [`GameEngine::import_leaderboard`] did with each row.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub added: usize,
+    pub duplicates: usize,
+    /// One message per rejected row, naming the row.
+    pub rejected: Vec<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -183,23 +356,266 @@ pub struct Leaderboards {
 
 impl Leaderboards {
     pub fn submit(&mut self, entry: LeaderboardEntry) {
+        let ranking = entry.mode.policy().ranking();
         let rows = self.by_mode.entry(entry.mode).or_default();
         rows.push(entry);
-        rows.sort_by(|a, b| compare_entries(a, b));
+        rows.sort_by(|a, b| ranking.compare(a, b));
+    }
+
+    /// Whether any mode's board holds the run `run_id`.
+    pub fn contains_run(&self, run_id: u64) -> bool {
+        self.by_mode
+            .values()
+            .flatten()
+            .any(|row| row.run_id == run_id)
     }
 
     pub fn rows(&self, mode: GameMode) -> &[LeaderboardEntry] {
         self.by_mode.get(&mode).map(Vec::as_slice).unwrap_or(&[])
     }
+
+    /// Ranked rows of `mode` that pass `filter`.
+    pub fn rows_matching<'a>(
+        &'a self,
+        mode: GameMode,
+        filter: &LeaderboardFilter,
+    ) -> impl Iterator<Item = &'a LeaderboardEntry> {
+        self.rows(mode).iter().filter(|row| filter.matches(row))
+    }
+
+    /// The best matching row for each loadout, in rank order.
+    pub fn best_per_loadout<'a>(
+        &'a self,
+        mode: GameMode,
+        filter: &LeaderboardFilter,
+    ) -> Vec<&'a LeaderboardEntry> {
+        let mut seen = BTreeSet::new();
+        self.rows_matching(mode, filter)
+            .filter(|row| seen.insert(row.loadout_summary.as_str()))
+            .collect()
+    }
+
+    /// Distinct loadout summaries on `mode`'s board, sorted.
+    pub fn loadouts(&self, mode: GameMode) -> Vec<String> {
+        let loadouts: BTreeSet<&str> = self
+            .rows(mode)
+            .iter()
+            .map(|row| row.loadout_summary.as_str())
+            .collect();
+        loadouts.into_iter().map(str::to_string).collect()
+    }
+
+    /// Distinct board sizes on `mode`'s board, smallest first.
+    pub fn boards(&self, mode: GameMode) -> Vec<Board> {
+        let mut boards: Vec<Board> = Vec::new();
+        for row in self.rows(mode) {
+            if !boards.contains(&row.board) {
+                boards.push(row.board);
+            }
+        }
+        boards.sort_by_key(|board| (board.width * board.height, board.width));
+        boards
+    }
 }
 
-fn compare_entries(a: &LeaderboardEntry, b: &LeaderboardEntry) -> Ordering {
-    match a.mode {
-        GameMode::Challenge => b
-            .survival_ticks
-            .cmp(&a.survival_ticks)
-            .then_with(|| b.score.cmp(&a.score)),
-        _ => b.score.cmp(&a.score),
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PointerControl {
+    Off,
+    #[default]
+    HoverSteer,
+    DragSteer,
+    ClickToTurn,
+    Swipe,
+}
+
+impl PointerControl {
+    pub const ALL: [PointerControl; 5] = [
+        PointerControl::Off,
+        PointerControl::HoverSteer,
+        PointerControl::DragSteer,
+        PointerControl::ClickToTurn,
+        PointerControl::Swipe,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeId {
+    #[default]
+    Classic,
+    Deuteranopia,
+    Protanopia,
+    HighContrast,
+}
+
+impl ThemeId {
+    pub const ALL: [ThemeId; 4] = [
+        ThemeId::Classic,
+        ThemeId::Deuteranopia,
+        ThemeId::Protanopia,
+        ThemeId::HighContrast,
+    ];
+}
+
+/// How the GUI draws the snake: gliding between cells or snapping per tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnakeRenderStyle {
+    #[default]
+    Smooth,
+    Classic,
+}
+
+/// Strength of particles, shake and flashes; `Off` suits motion-sensitive players.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EffectsIntensity {
+    Off,
+    Low,
+    #[default]
+    Full,
+}
+
+impl EffectsIntensity {
+    pub const ALL: [EffectsIntensity; 3] = [
+        EffectsIntensity::Off,
+        EffectsIntensity::Low,
+        EffectsIntensity::Full,
+    ];
+}
+
+/// Preset tuning layered over every mode's rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Insane,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Insane,
+    ];
+
+    /// Speed at level zero, before the mode's curve and any boosts.
+    pub fn start_speed_percent(self) -> u32 {
+        match self {
+            Difficulty::Easy => 80,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 120,
+            Difficulty::Insane => 145,
+        }
+    }
+
+    pub fn initial_grace_ticks(self) -> u8 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => 3,
+            Difficulty::Hard => 2,
+            Difficulty::Insane => 1,
+        }
+    }
+
+    pub fn reposition_grace_ticks(self) -> u8 {
+        match self {
+            Difficulty::Easy => 3,
+            Difficulty::Normal => 2,
+            Difficulty::Hard | Difficulty::Insane => 1,
+        }
+    }
+
+    /// Scale applied to the mode's food counts, in percent.
+    pub fn food_density_percent(self) -> u32 {
+        match self {
+            Difficulty::Easy => 150,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 75,
+            Difficulty::Insane => 50,
+        }
+    }
+
+    /// Easy lets the snake pass through walls without the soft-wrap tool.
+    pub fn wraps_walls(self) -> bool {
+        self == Difficulty::Easy
+    }
+}
+
+/// Mixer levels in whole percent; effective gains multiply by the master level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioVolume {
+    pub master_percent: u32,
+    pub sfx_percent: u32,
+    pub music_percent: u32,
+}
+
+impl Default for AudioVolume {
+    fn default() -> Self {
+        Self {
+            master_percent: 80,
+            sfx_percent: 100,
+            music_percent: 60,
+        }
+    }
+}
+
+impl AudioVolume {
+    pub fn sfx_gain(&self) -> f32 {
+        (self.master_percent * self.sfx_percent) as f32 / 10_000.0
+    }
+
+    pub fn music_gain(&self) -> f32 {
+        (self.master_percent * self.music_percent) as f32 / 10_000.0
+    }
+}
+
+/// Pointer timings and distances tunable from Settings, kept in integer
+/// milliseconds and pixels so profiles stay comparable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointerTuning {
+    pub idle_pause_ms: u32,
+    pub displacement_px: u32,
+    pub dwell_ms: u32,
+    pub idle_grace_ms: u32,
+    pub swipe_min_px: u32,
+    pub swipe_min_px_per_second: u32,
+    pub tap_max_ms: u32,
+}
+
+impl Default for PointerTuning {
+    fn default() -> Self {
+        Self {
+            idle_pause_ms: 10,
+            displacement_px: 2,
+            dwell_ms: 450,
+            idle_grace_ms: 200,
+            swipe_min_px: 30,
+            swipe_min_px_per_second: 250,
+            tap_max_ms: 250,
+        }
+    }
+}
+
+impl PointerTuning {
+    pub fn idle_pause_seconds(&self) -> f32 {
+        self.idle_pause_ms as f32 / 1000.0
+    }
+
+    pub fn displacement_threshold(&self) -> f32 {
+        self.displacement_px as f32
+    }
+
+    pub fn dwell_seconds(&self) -> f32 {
+        self.dwell_ms as f32 / 1000.0
+    }
+
+    pub fn idle_grace_seconds(&self) -> f32 {
+        self.idle_grace_ms as f32 / 1000.0
+    }
+
+    pub fn tap_max_seconds(&self) -> f32 {
+        self.tap_max_ms as f32 / 1000.0
     }
 }
 
@@ -213,28 +629,60 @@ pub struct LegacyProfile {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Profile {
     pub schema_version: u32,
+    /// Player name shown in menus and on leaderboard rows.
+    pub name: String,
     pub replay_on_death: bool,
     pub invincible_cumulative_length: u64,
     pub unlocked_tool_ids: BTreeSet<String>,
     pub old_best_score: Option<u64>,
+    pub pointer_controls: BTreeMap<GameMode, PointerControl>,
+    pub pointer_tuning: PointerTuning,
+    pub show_dpad: bool,
+    pub theme: ThemeId,
+    pub snake_render: SnakeRenderStyle,
+    pub effects_intensity: EffectsIntensity,
+    pub audio_volume: AudioVolume,
+    pub difficulty: Difficulty,
+    /// Best stars earned per puzzle id; unsolved puzzles are absent.
+    pub puzzle_stars: BTreeMap<String, u8>,
+    /// Ids of cleared campaign stages. Added in schema 3.
+    pub campaign_cleared: BTreeSet<String>,
+    pub custom_presets: Vec<CustomRules>,
+    /// Runs started on this profile; numbers each run's id.
+    pub runs_started: u64,
 }
 
 impl Default for Profile {
     fn default() -> Self {
         Self {
             schema_version: CURRENT_SCHEMA_VERSION,
+            name: DEFAULT_PROFILE_NAME.to_string(),
             replay_on_death: false,
             invincible_cumulative_length: 0,
             unlocked_tool_ids: BTreeSet::new(),
             old_best_score: None,
+            pointer_controls: BTreeMap::new(),
+            pointer_tuning: PointerTuning::default(),
+            show_dpad: false,
+            theme: ThemeId::default(),
+            snake_render: SnakeRenderStyle::default(),
+            effects_intensity: EffectsIntensity::default(),
+            audio_volume: AudioVolume::default(),
+            difficulty: Difficulty::default(),
+            puzzle_stars: BTreeMap::new(),
+            campaign_cleared: BTreeSet::new(),
+            custom_presets: Vec::new(),
+            runs_started: 0,
         }
     }
 }
 
 impl Profile {
     pub fn from_legacy(legacy: LegacyProfile) -> Result<Self, String> {
-        let mut profile = Self::default();
-        profile.schema_version = legacy.schema_version.unwrap_or(1);
+        let mut profile = Self {
+            schema_version: legacy.schema_version.unwrap_or(1),
+            ..Self::default()
+        };
         if profile.schema_version > CURRENT_SCHEMA_VERSION {
             return Err(format!(
                 "profile schema {} is newer than supported {}",
@@ -246,20 +694,76 @@ impl Profile {
         migrate_profile(profile)
     }
 
+    pub fn pointer_control(&self, mode: GameMode) -> PointerControl {
+        self.pointer_controls
+            .get(&mode)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn puzzle_stars(&self, id: &str) -> u8 {
+        self.puzzle_stars.get(id).copied().unwrap_or(0)
+    }
+
+    /// Stage `index` is playable once every earlier stage is cleared.
+    pub fn campaign_stage_unlocked(&self, index: usize) -> bool {
+        index < STAGES.len()
+            && STAGES[..index]
+                .iter()
+                .all(|stage| self.campaign_cleared.contains(stage.id))
+    }
+
+    /// Saves `rules` as a preset, replacing any preset with the same name.
+    pub fn save_custom_preset(&mut self, rules: CustomRules) {
+        match self
+            .custom_presets
+            .iter_mut()
+            .find(|preset| preset.name == rules.name)
+        {
+            Some(preset) => *preset = rules,
+            None => self.custom_presets.push(rules),
+        }
+    }
+
+    pub fn set_pointer_control(&mut self, mode: GameMode, control: PointerControl) {
+        self.pointer_controls.insert(mode, control);
+    }
+
     pub fn apply_threshold_unlocks(&mut self, registry: &ToolRegistry, thresholds: &[u64]) {
         let mut next = BTreeSet::new();
         for tool in registry.list() {
-            if let Some(threshold) = tool.unlock_threshold {
-                if thresholds.contains(&threshold) && self.invincible_cumulative_length >= threshold
-                {
-                    next.insert(tool.id.clone());
-                }
+            if let Some(threshold) = tool.unlock_threshold
+                && thresholds.contains(&threshold)
+                && self.invincible_cumulative_length >= threshold
+            {
+                next.insert(tool.id.clone());
             }
         }
         self.unlocked_tool_ids = next;
     }
 }
 
+/// Trims `name` and checks it is 1 to [`MAX_PROFILE_NAME_LEN`] letters,
+/// digits, spaces, `-` or `_`.
+pub fn validate_profile_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("profile name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_PROFILE_NAME_LEN {
+        return Err(format!(
+            "profile name is longer than {MAX_PROFILE_NAME_LEN} characters"
+        ));
+    }
+    if let Some(bad) = name
+        .chars()
+        .find(|c| !(c.is_alphanumeric() || matches!(c, ' ' | '-' | '_')))
+    {
+        return Err(format!("profile name cannot contain '{bad}'"));
+    }
+    Ok(name.to_string())
+}
+
 pub fn migrate_profile(mut profile: Profile) -> Result<Profile, String> {
     if profile.schema_version > CURRENT_SCHEMA_VERSION {
         return Err(format!(
@@ -269,8 +773,19 @@ pub fn migrate_profile(mut profile: Profile) -> Result<Profile, String> {
     }
     if profile.schema_version < CURRENT_SCHEMA_VERSION {
         profile.replay_on_death = profile.replay_on_death && profile.schema_version >= 1;
-        profile.schema_version = CURRENT_SCHEMA_VERSION;
     }
+    if profile.schema_version < 3 {
+        // Campaign progress is new in schema 3; players who already earned
+        // tools skip the opening stage. Unlocks are only re-derived after
+        // migration, so go by the length that earns them.
+        profile.campaign_cleared.clear();
+        if profile.invincible_cumulative_length >= DEFAULT_THRESHOLDS[0]
+            || !profile.unlocked_tool_ids.is_empty()
+        {
+            profile.campaign_cleared.insert(STAGES[0].id.to_string());
+        }
+    }
+    profile.schema_version = CURRENT_SCHEMA_VERSION;
     Ok(profile)
 }
 
@@ -279,12 +794,140 @@ pub trait ModePolicy {
     fn collision_outcome(&self) -> CollisionOutcome;
     fn score(&self, metrics: &RunMetrics, effects: &ActiveEffects) -> u64;
     fn run_end_state(&self, replay_on_death: bool) -> RunEnd;
+
+    /// How the mode's leaderboard is ordered.
+    fn ranking(&self) -> Ranking {
+        Ranking::by(RankKey::highest(RankField::Score)).build()
+    }
+
+    /// Extra points for eating `kind` on top of the mode's per-food score.
+    fn food_bonus(&self, kind: FoodKind) -> u64 {
+        match kind {
+            FoodKind::Normal => 0,
+            FoodKind::Golden => 30,
+            FoodKind::Shrinking => 5,
+            FoodKind::SpeedUp => 10,
+            FoodKind::Bonus => 50,
+        }
+    }
+
+    /// Default food layout for the mode; the engine may override it.
+    fn food_rules(&self) -> FoodRules {
+        FoodRules::multi_food()
+    }
+
+    /// Awarded once for filling `board`.
+    fn completion_bonus(&self, board: Board) -> u64 {
+        (board.width.max(0) as u64)
+            .saturating_mul(board.height.max(0) as u64)
+            .saturating_mul(10)
+    }
+
+    /// The run's score including the completion bonus for a filled board.
+    fn final_score(&self, run: &GameRun) -> u64 {
+        let score = self.score(&run.metrics, &run.effects);
+        if run.board_filled {
+            score.saturating_add(self.completion_bonus(run.board))
+        } else {
+            score
+        }
+    }
+
+    /// How the mode ramps up speed during a run.
+    fn speed_curve(&self) -> SpeedCurve {
+        SpeedCurve::Flat
+    }
+
+    /// Clock the run starts with, in ticks; `None` for untimed modes.
+    fn time_limit_ticks(&self) -> Option<u64> {
+        None
+    }
+
+    /// Ticks added to the clock for eating `kind`.
+    fn time_bonus_ticks(&self, _kind: FoodKind) -> u64 {
+        0
+    }
+
+    /// Hazards that appear as the run goes on; `None` keeps the board as is.
+    fn arena_schedule(&self) -> Option<ArenaSchedule> {
+        None
+    }
+
+    /// Points for time spent at raised speed.
+    fn speed_bonus_points(&self, metrics: &RunMetrics) -> u64 {
+        metrics.speed_level_ticks / 10
+    }
+
+    fn food_bonus_points(&self, metrics: &RunMetrics) -> u64 {
+        metrics
+            .eaten_by_kind
+            .iter()
+            .map(|(kind, count)| self.food_bonus(*kind).saturating_mul(*count))
+            .fold(0, u64::saturating_add)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeedCurve {
+    Flat,
+    /// One level every `every_ticks` survival ticks.
+    ByTime {
+        every_ticks: u64,
+        max_level: u32,
+    },
+    /// One level every `every_growth` growth units.
+    ByLength {
+        every_growth: u64,
+        max_level: u32,
+    },
+}
+
+impl SpeedCurve {
+    pub fn level(&self, metrics: &RunMetrics) -> u32 {
+        let (progress, every, max_level) = match *self {
+            SpeedCurve::Flat => return 0,
+            SpeedCurve::ByTime {
+                every_ticks,
+                max_level,
+            } => (metrics.survival_ticks, every_ticks, max_level),
+            SpeedCurve::ByLength {
+                every_growth,
+                max_level,
+            } => (metrics.growth_units, every_growth, max_level),
+        };
+        (progress / every.max(1)).min(max_level as u64) as u32
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RunEnd {
     Continue,
-    End { show_replay: bool },
+    End {
+        show_replay: bool,
+    },
+    /// The snake filled the board.
+    Won,
+    /// A timed mode's clock reached zero.
+    TimeUp,
+    /// A puzzle's move budget ran out before its goal was met.
+    OutOfMoves,
+}
+
+/// Something noteworthy that happened during a run, queued for presentation
+/// layers (effects, audio) to drain after each tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunEvent {
+    FoodEaten { at: Point, kind: FoodKind },
+    GraceStarted { ticks: u8 },
+    Repositioned { from: Point, to: Point },
+    Died { at: Point },
+    BoardFilled { length: usize },
+    TimeUp,
+    PuzzleSolved { stars: u8 },
+    OutOfMoves,
+    StageCleared { stage: usize },
+    HazardAppeared { at: Point },
+    BorderClosed { inset: i32 },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -297,35 +940,63 @@ pub struct ActiveEffects {
 }
 
 fn effects_from_loadout(loadout: Option<&ToolLoadout>) -> ActiveEffects {
+    effects_from_tools(loadout.into_iter().flat_map(|loadout| &loadout.slots))
+}
+
+pub(crate) fn effects_from_tools(
+    tools: impl IntoIterator<Item = impl AsRef<str>>,
+) -> ActiveEffects {
     let mut effects = ActiveEffects::default();
-    if let Some(loadout) = loadout {
-        for slot in &loadout.slots {
-            match slot.as_str() {
-                "turn-buffer" => effects.has_turn_buffer = true,
-                "slow-window" => effects.has_slow_window = true,
-                "soft-wrap" => {
-                    effects.has_soft_wrap = true;
-                    effects.score_bonus_percent += 5;
-                }
-                "rewind-step" => {
-                    effects.has_rewind_step = true;
-                    effects.score_bonus_percent += 10;
-                }
-                _ => {}
+    for tool in tools {
+        match tool.as_ref() {
+            "turn-buffer" => effects.has_turn_buffer = true,
+            "slow-window" => effects.has_slow_window = true,
+            "soft-wrap" => {
+                effects.has_soft_wrap = true;
+                effects.score_bonus_percent += 5;
             }
+            "rewind-step" => {
+                effects.has_rewind_step = true;
+                effects.score_bonus_percent += 10;
+            }
+            _ => {}
         }
     }
     effects
 }
 
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Id for the `serial`-th run on a profile, mixed with the start time in
+/// nanoseconds so runs from other machines don't collide with it.
+fn new_run_id(serial: u64) -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    food::lcg_next(food::lcg_next(nanos) ^ serial)
+}
+
 pub struct PracticePolicy;
 pub struct ChallengePolicy;
 pub struct ExperimentalPolicy;
 pub struct InvinciblePolicy;
+pub struct TimeAttackPolicy;
+pub struct PuzzlePolicy;
+pub struct CampaignPolicy;
+pub struct ArenaPolicy;
+pub struct CustomPolicy {
+    pub rules: CustomRules,
+}
 
 impl ModePolicy for PracticePolicy {
     fn mode(&self) -> GameMode {
-        GameMode::Practice
+        GameMode::PRACTICE
     }
 
     fn collision_outcome(&self) -> CollisionOutcome {
@@ -333,7 +1004,7 @@ impl ModePolicy for PracticePolicy {
     }
 
     fn score(&self, metrics: &RunMetrics, _effects: &ActiveEffects) -> u64 {
-        metrics.food_eaten * 10
+        (metrics.food_eaten * 10).saturating_add(self.food_bonus_points(metrics))
     }
 
     fn run_end_state(&self, replay_on_death: bool) -> RunEnd {
@@ -341,11 +1012,17 @@ impl ModePolicy for PracticePolicy {
             show_replay: replay_on_death,
         }
     }
+
+    fn ranking(&self) -> Ranking {
+        Ranking::by(RankKey::highest(RankField::Score))
+            .then(RankKey::highest(RankField::Food))
+            .build()
+    }
 }
 
 impl ModePolicy for ChallengePolicy {
     fn mode(&self) -> GameMode {
-        GameMode::Challenge
+        GameMode::CHALLENGE
     }
 
     fn collision_outcome(&self) -> CollisionOutcome {
@@ -356,7 +1033,9 @@ impl ModePolicy for ChallengePolicy {
         let base = metrics
             .survival_ticks
             .saturating_mul(1_000)
-            .saturating_add(metrics.food_eaten * 10);
+            .saturating_add(metrics.food_eaten * 10)
+            .saturating_add(self.food_bonus_points(metrics))
+            .saturating_add(self.speed_bonus_points(metrics));
         base.saturating_add(base.saturating_mul(effects.score_bonus_percent) / 100)
     }
 
@@ -365,11 +1044,39 @@ impl ModePolicy for ChallengePolicy {
             show_replay: replay_on_death,
         }
     }
+
+    fn food_rules(&self) -> FoodRules {
+        FoodRules::single_apple()
+    }
+
+    fn speed_curve(&self) -> SpeedCurve {
+        SpeedCurve::ByTime {
+            every_ticks: 100,
+            max_level: 10,
+        }
+    }
+
+    /// Survival dominates Challenge scores, so risky pickups pay out more.
+    fn food_bonus(&self, kind: FoodKind) -> u64 {
+        match kind {
+            FoodKind::Normal => 0,
+            FoodKind::Golden => 20,
+            FoodKind::Shrinking => 0,
+            FoodKind::SpeedUp => 40,
+            FoodKind::Bonus => 30,
+        }
+    }
+
+    fn ranking(&self) -> Ranking {
+        Ranking::by(RankKey::highest(RankField::Survival))
+            .then(RankKey::highest(RankField::Score))
+            .build()
+    }
 }
 
 impl ModePolicy for ExperimentalPolicy {
     fn mode(&self) -> GameMode {
-        GameMode::Experimental
+        GameMode::EXPERIMENTAL
     }
 
     fn collision_outcome(&self) -> CollisionOutcome {
@@ -380,7 +1087,9 @@ impl ModePolicy for ExperimentalPolicy {
         let base = metrics
             .food_eaten
             .saturating_mul(12)
-            .saturating_add(metrics.survival_ticks / 5);
+            .saturating_add(metrics.survival_ticks / 5)
+            .saturating_add(self.food_bonus_points(metrics))
+            .saturating_add(self.speed_bonus_points(metrics));
         base.saturating_add(base.saturating_mul(effects.score_bonus_percent) / 100)
     }
 
@@ -389,11 +1098,28 @@ impl ModePolicy for ExperimentalPolicy {
             show_replay: replay_on_death,
         }
     }
+
+    fn food_rules(&self) -> FoodRules {
+        FoodRules::cluster()
+    }
+
+    fn speed_curve(&self) -> SpeedCurve {
+        SpeedCurve::ByLength {
+            every_growth: 5,
+            max_level: 8,
+        }
+    }
+
+    fn ranking(&self) -> Ranking {
+        Ranking::by(RankKey::highest(RankField::Score))
+            .then(RankKey::highest(RankField::Survival))
+            .build()
+    }
 }
 
 impl ModePolicy for InvinciblePolicy {
     fn mode(&self) -> GameMode {
-        GameMode::Invincible
+        GameMode::INVINCIBLE
     }
 
     fn collision_outcome(&self) -> CollisionOutcome {
@@ -404,48 +1130,588 @@ impl ModePolicy for InvinciblePolicy {
         let base = metrics
             .food_eaten
             .saturating_mul(8)
-            .saturating_add(metrics.survival_ticks / 10);
+            .saturating_add(metrics.survival_ticks / 10)
+            .saturating_add(self.food_bonus_points(metrics))
+            .saturating_add(self.speed_bonus_points(metrics));
         base.saturating_add(base.saturating_mul(effects.score_bonus_percent) / 100)
     }
 
     fn run_end_state(&self, _replay_on_death: bool) -> RunEnd {
         RunEnd::Continue
     }
-}
 
-pub fn policy_for(mode: GameMode) -> Box<dyn ModePolicy> {
-    match mode {
-        GameMode::Practice => Box::new(PracticePolicy),
-        GameMode::Challenge => Box::new(ChallengePolicy),
-        GameMode::Experimental => Box::new(ExperimentalPolicy),
-        GameMode::Invincible => Box::new(InvinciblePolicy),
+    fn food_rules(&self) -> FoodRules {
+        FoodRules::timed_waves()
     }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct GameRun {
-    pub mode: GameMode,
-    pub board: Board,
-    pub snake: Vec<Point>,
-    pub metrics: RunMetrics,
-    pub ended: bool,
-    pub show_replay: bool,
-    pub grace_ticks_remaining: u8,
-    pub active_loadout: Option<ToolLoadout>,
-    pub effects: ActiveEffects,
-}
 
-impl GameRun {
-    pub fn tick(&mut self) {
-        self.metrics.survival_ticks = self.metrics.survival_ticks.saturating_add(1);
-        if self.grace_ticks_remaining > 0 {
-            self.grace_ticks_remaining -= 1;
+    fn speed_curve(&self) -> SpeedCurve {
+        SpeedCurve::ByLength {
+            every_growth: 10,
+            max_level: 6,
         }
     }
 
-    pub fn add_food(&mut self, growth: u64) {
-        self.metrics.food_eaten = self.metrics.food_eaten.saturating_add(1);
-        self.metrics.growth_units = self.metrics.growth_units.saturating_add(growth);
+    /// Invincible rewards length, so shrinking costs points instead of earning them.
+    fn food_bonus(&self, kind: FoodKind) -> u64 {
+        match kind {
+            FoodKind::Normal | FoodKind::Shrinking => 0,
+            FoodKind::Golden => 40,
+            FoodKind::SpeedUp => 5,
+            FoodKind::Bonus => 25,
+        }
+    }
+
+    /// Length is the point of Invincible, so it settles equal scores.
+    fn ranking(&self) -> Ranking {
+        Ranking::by(RankKey::highest(RankField::Score))
+            .then(RankKey::highest(RankField::Growth))
+            .then(RankKey::highest(RankField::Food))
+            .build()
+    }
+}
+
+/// Ticks a time-attack run starts with, about 108 seconds at the base tick rate.
+pub const TIME_ATTACK_BUDGET_TICKS: u64 = 600;
+
+impl ModePolicy for TimeAttackPolicy {
+    fn mode(&self) -> GameMode {
+        GameMode::TIME_ATTACK
+    }
+
+    fn collision_outcome(&self) -> CollisionOutcome {
+        CollisionOutcome::Die
+    }
+
+    /// Only food counts; the clock, not survival, is what the player spends.
+    fn score(&self, metrics: &RunMetrics, _effects: &ActiveEffects) -> u64 {
+        metrics
+            .food_eaten
+            .saturating_mul(20)
+            .saturating_add(self.food_bonus_points(metrics))
+    }
+
+    fn run_end_state(&self, replay_on_death: bool) -> RunEnd {
+        RunEnd::End {
+            show_replay: replay_on_death,
+        }
+    }
+
+    fn time_limit_ticks(&self) -> Option<u64> {
+        Some(TIME_ATTACK_BUDGET_TICKS)
+    }
+
+    fn time_bonus_ticks(&self, kind: FoodKind) -> u64 {
+        match kind {
+            FoodKind::Normal => 15,
+            FoodKind::Golden => 40,
+            FoodKind::Shrinking => 20,
+            FoodKind::SpeedUp => 10,
+            FoodKind::Bonus => 30,
+        }
+    }
+
+    /// Equal scores rank the run that needed less clock first.
+    fn ranking(&self) -> Ranking {
+        Ranking::by(RankKey::highest(RankField::Score))
+            .then(RankKey::lowest(RankField::Survival))
+            .build()
+    }
+}
+
+impl ModePolicy for PuzzlePolicy {
+    fn mode(&self) -> GameMode {
+        GameMode::PUZZLE
+    }
+
+    fn collision_outcome(&self) -> CollisionOutcome {
+        CollisionOutcome::Die
+    }
+
+    fn score(&self, metrics: &RunMetrics, _effects: &ActiveEffects) -> u64 {
+        metrics.food_eaten * 10
+    }
+
+    fn run_end_state(&self, replay_on_death: bool) -> RunEnd {
+        RunEnd::End {
+            show_replay: replay_on_death,
+        }
+    }
+
+    /// Hand-placed food only; nothing spawns during the run.
+    fn food_rules(&self) -> FoodRules {
+        FoodRules::fixed()
+    }
+
+    /// Solved puzzles score by stars, then by moves left over.
+    fn final_score(&self, run: &GameRun) -> u64 {
+        match run.puzzle {
+            Some(active) if run.won => {
+                active.puzzle.stars(active.moves_used) as u64 * 100
+                    + active.moves_remaining() as u64 * 10
+            }
+            _ => self.score(&run.metrics, &run.effects),
+        }
+    }
+}
+
+impl ModePolicy for CampaignPolicy {
+    fn mode(&self) -> GameMode {
+        GameMode::CAMPAIGN
+    }
+
+    fn collision_outcome(&self) -> CollisionOutcome {
+        CollisionOutcome::Die
+    }
+
+    fn score(&self, metrics: &RunMetrics, _effects: &ActiveEffects) -> u64 {
+        (metrics.food_eaten * 10)
+            .saturating_add(metrics.survival_ticks / 10)
+            .saturating_add(self.food_bonus_points(metrics))
+            .saturating_add(self.speed_bonus_points(metrics))
+    }
+
+    fn run_end_state(&self, replay_on_death: bool) -> RunEnd {
+        RunEnd::End {
+            show_replay: replay_on_death,
+        }
+    }
+}
+
+impl ModePolicy for ArenaPolicy {
+    fn mode(&self) -> GameMode {
+        GameMode::ARENA
+    }
+
+    fn collision_outcome(&self) -> CollisionOutcome {
+        CollisionOutcome::Die
+    }
+
+    /// Each tick is worth more the more hazards the snake is dodging.
+    fn score(&self, metrics: &RunMetrics, _effects: &ActiveEffects) -> u64 {
+        metrics
+            .survival_ticks
+            .saturating_mul(10)
+            .saturating_add(metrics.hazard_ticks)
+            .saturating_add(metrics.food_eaten * 10)
+            .saturating_add(self.food_bonus_points(metrics))
+    }
+
+    fn run_end_state(&self, replay_on_death: bool) -> RunEnd {
+        RunEnd::End {
+            show_replay: replay_on_death,
+        }
+    }
+
+    fn food_rules(&self) -> FoodRules {
+        FoodRules::multi_food()
+    }
+
+    fn arena_schedule(&self) -> Option<ArenaSchedule> {
+        Some(ARENA_SCHEDULE)
+    }
+
+    fn ranking(&self) -> Ranking {
+        Ranking::by(RankKey::highest(RankField::Survival))
+            .then(RankKey::highest(RankField::Score))
+            .build()
+    }
+}
+
+impl ModePolicy for CustomPolicy {
+    fn mode(&self) -> GameMode {
+        GameMode::CUSTOM
+    }
+
+    fn collision_outcome(&self) -> CollisionOutcome {
+        self.rules.collision
+    }
+
+    fn score(&self, metrics: &RunMetrics, effects: &ActiveEffects) -> u64 {
+        let weights = self.rules.weights;
+        let base = metrics
+            .food_eaten
+            .saturating_mul(weights.per_food.into())
+            .saturating_add(
+                (metrics.survival_ticks / 10).saturating_mul(weights.per_ten_ticks.into()),
+            )
+            .saturating_add(
+                self.speed_bonus_points(metrics)
+                    .saturating_mul(weights.per_speed_point.into()),
+            )
+            .saturating_add(self.food_bonus_points(metrics));
+        base.saturating_add(base.saturating_mul(effects.score_bonus_percent) / 100)
+    }
+
+    /// Repositioning runs only end when the player leaves, like Invincible.
+    fn run_end_state(&self, replay_on_death: bool) -> RunEnd {
+        match self.rules.collision {
+            CollisionOutcome::Die => RunEnd::End {
+                show_replay: replay_on_death,
+            },
+            CollisionOutcome::Reposition => RunEnd::Continue,
+        }
+    }
+
+    fn food_rules(&self) -> FoodRules {
+        self.rules.food_rules
+    }
+
+    fn speed_curve(&self) -> SpeedCurve {
+        self.rules.speed_curve
+    }
+
+    fn ranking(&self) -> Ranking {
+        Ranking::by(RankKey::highest(RankField::Score))
+            .then(RankKey::highest(RankField::Food))
+            .build()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRun {
+    /// Assigned at start and carried by the run's leaderboard entry.
+    pub run_id: u64,
+    pub mode: GameMode,
+    pub board: Board,
+    pub snake: Vec<Point>,
+    pub metrics: RunMetrics,
+    pub ended: bool,
+    pub show_replay: bool,
+    pub grace_ticks_remaining: u8,
+    pub active_loadout: Option<ToolLoadout>,
+    pub effects: ActiveEffects,
+    pub abandoned: bool,
+    pub events: Vec<RunEvent>,
+    /// Segments still to be added by skipping tail removal on upcoming moves.
+    pub pending_growth: u64,
+    pub speed_boost_ticks: u32,
+    pub foods: Vec<Food>,
+    pub food_rules: FoodRules,
+    pub food_seed: u64,
+    /// The run ended in success: a filled board, a cleared stage or a solved
+    /// puzzle.
+    pub won: bool,
+    /// The snake filled the board; only this earns the completion bonus.
+    pub board_filled: bool,
+    pub speed_curve: SpeedCurve,
+    pub difficulty: Difficulty,
+    /// Clock left in timed modes, counted in ticks.
+    pub time_remaining_ticks: Option<u64>,
+    pub timed_out: bool,
+    /// Cells that kill on contact, on top of the board edges.
+    pub walls: Vec<Point>,
+    pub puzzle: Option<ActivePuzzle>,
+    pub out_of_moves: bool,
+    /// Index into [`STAGES`] for campaign runs.
+    pub campaign_stage: Option<usize>,
+    /// Speed at level zero, before the speed curve and boosts.
+    pub start_speed_percent: u32,
+    pub arena: Option<Arena>,
+    /// Rules of a custom run; they replace the mode's policy.
+    pub custom: Option<CustomRules>,
+}
+
+impl GameRun {
+    pub fn tick(&mut self) {
+        self.metrics.survival_ticks = self.metrics.survival_ticks.saturating_add(1);
+        if self.grace_ticks_remaining > 0 {
+            self.grace_ticks_remaining -= 1;
+        }
+        self.speed_boost_ticks = self.speed_boost_ticks.saturating_sub(1);
+        if let Some(remaining) = self.time_remaining_ticks.as_mut() {
+            *remaining = remaining.saturating_sub(1);
+        }
+        self.metrics.speed_level_ticks = self
+            .metrics
+            .speed_level_ticks
+            .saturating_add(self.speed_level() as u64);
+    }
+
+    pub fn add_food(&mut self, growth: u64) {
+        self.record_food(FoodKind::Normal, growth);
+    }
+
+    /// Applies eating `kind` with the head already on the food's cell.
+    pub fn eat_food(&mut self, kind: FoodKind) {
+        let growth = kind.growth();
+        self.record_food(kind, growth);
+        self.pending_growth = self.pending_growth.saturating_add(growth);
+        let time_bonus = self.policy().time_bonus_ticks(kind);
+        if let Some(remaining) = self.time_remaining_ticks.as_mut() {
+            *remaining = remaining.saturating_add(time_bonus);
+        }
+        match kind {
+            FoodKind::Shrinking => {
+                // Leave one spare segment for the tail step that follows.
+                let keep = self
+                    .snake
+                    .len()
+                    .saturating_sub(food::SHRINK_SEGMENTS)
+                    .max(food::MIN_SNAKE_LENGTH + 1);
+                self.snake.truncate(keep);
+            }
+            FoodKind::SpeedUp => self.speed_boost_ticks = food::SPEED_UP_TICKS,
+            FoodKind::Normal | FoodKind::Golden | FoodKind::Bonus => {}
+        }
+    }
+
+    /// Finishes a move: drops the tail unless growth is still pending.
+    pub fn trim_tail(&mut self) {
+        if self.pending_growth > 0 {
+            self.pending_growth -= 1;
+        } else {
+            self.snake.pop();
+        }
+    }
+
+    /// Clears the board and lays out the opening food from `seed`, which
+    /// also seeds arena hazard placement.
+    pub fn seed_food(&mut self, seed: u64) {
+        self.food_seed = seed;
+        if let Some(arena) = self.arena.as_mut() {
+            arena.seed = seed;
+        }
+        if self.food_rules.pattern == FoodPattern::Fixed {
+            return;
+        }
+        self.foods.clear();
+        self.spawn_food(self.food_rules.initial_count);
+    }
+
+    /// Adds up to `count` foods, respecting the board cap. Returns
+    /// [`FoodPlacement::BoardFull`] if the board ran out of free cells.
+    pub fn spawn_food(&mut self, count: usize) -> Option<FoodPlacement> {
+        let room = self
+            .food_rules
+            .max_on_board
+            .saturating_sub(self.foods.len());
+        let mut group = Vec::new();
+        let mut blocked = self.snake.clone();
+        blocked.extend(&self.walls);
+        if let Some(arena) = &self.arena {
+            blocked.extend(arena.roamers.iter().map(|roamer| roamer.position));
+        }
+        for _ in 0..count.min(room) {
+            let occupied: Vec<Point> = self.foods.iter().map(|food| food.position).collect();
+            let clustered = match self.food_rules.pattern {
+                FoodPattern::Cluster => {
+                    food::free_neighbour(self.board, &blocked, &occupied, &group)
+                }
+                _ => None,
+            };
+            let position = match clustered {
+                Some(position) => position,
+                None => {
+                    let (placement, seed) = food::next_food_position(
+                        self.food_seed,
+                        self.board,
+                        &blocked,
+                        &occupied,
+                        self.food_rules.keep_apart,
+                    );
+                    self.food_seed = seed;
+                    match placement {
+                        FoodPlacement::Cell(position) => position,
+                        FoodPlacement::BoardFull => return Some(FoodPlacement::BoardFull),
+                    }
+                }
+            };
+            self.food_seed = food::lcg_next(self.food_seed);
+            let kind = if self.food_rules.special_kinds {
+                FoodKind::from_roll(self.food_seed >> 33)
+            } else {
+                FoodKind::Normal
+            };
+            group.push(position);
+            self.foods
+                .push(Food::new(kind, position, self.metrics.survival_ticks));
+        }
+        group.last().map(|position| FoodPlacement::Cell(*position))
+    }
+
+    /// Per-tick food upkeep: replaces expired food and releases waves.
+    pub fn update_food(&mut self) {
+        let tick = self.metrics.survival_ticks;
+        let before = self.foods.len();
+        self.foods.retain(|food| !food.is_expired(tick));
+        let expired = before - self.foods.len();
+        self.spawn_food(expired + self.food_rules.wave_at(tick));
+    }
+
+    /// Moves roamers and adds whatever hazards are due this tick. A border
+    /// ring only closes once the snake is clear of it.
+    pub fn update_arena(&mut self) {
+        let Some(mut arena) = self.arena.take() else {
+            return;
+        };
+        let tick = self.metrics.survival_ticks;
+        let schedule = arena.schedule;
+        if tick.is_multiple_of(schedule.roamer_step_ticks) {
+            for index in 0..arena.roamers.len() {
+                let others = arena.roamers.clone();
+                arena.roamers[index].step(|cell| {
+                    self.hits_wall(cell)
+                        || self.snake.contains(&cell)
+                        || self.foods.iter().any(|food| food.position == cell)
+                        || others.iter().any(|roamer| roamer.position == cell)
+                });
+            }
+        }
+
+        if tick.is_multiple_of(schedule.shrink_every_ticks)
+            && let Some(ring) = arena.next_ring(self.board)
+            && !ring.iter().any(|cell| self.snake.contains(cell))
+        {
+            self.foods.retain(|food| !ring.contains(&food.position));
+            arena
+                .roamers
+                .retain(|roamer| !ring.contains(&roamer.position));
+            self.walls.extend(ring);
+            arena.inset += 1;
+            arena.level += 1;
+            self.events
+                .push(RunEvent::BorderClosed { inset: arena.inset });
+        }
+        let spawn_wall = tick.is_multiple_of(schedule.wall_every_ticks);
+        let spawn_roamer = tick.is_multiple_of(schedule.roamer_every_ticks)
+            && arena.roamers.len() < schedule.max_roamers;
+        for is_wall in [true, false] {
+            if !(if is_wall { spawn_wall } else { spawn_roamer }) {
+                continue;
+            }
+            let (picked, seed) =
+                arena::pick_cell(arena.seed, self.board, arena.inset, self.snake[0], |cell| {
+                    self.walls.contains(&cell)
+                        || self.snake.contains(&cell)
+                        || self.foods.iter().any(|food| food.position == cell)
+                        || arena.has_roamer_at(cell)
+                });
+            arena.seed = seed;
+            let Some(at) = picked else {
+                continue;
+            };
+            if is_wall {
+                self.walls.push(at);
+            } else {
+                arena.roamers.push(Roamer {
+                    position: at,
+                    dx: 1,
+                    dy: 1,
+                });
+            }
+            arena.level += 1;
+            self.events.push(RunEvent::HazardAppeared { at });
+        }
+        self.metrics.hazard_ticks = self.metrics.hazard_ticks.saturating_add(arena.level as u64);
+        self.arena = Some(arena);
+    }
+
+    /// Removes and returns the food at `at`, if any.
+    pub fn take_food_at(&mut self, at: Point) -> Option<Food> {
+        let index = self.foods.iter().position(|food| food.position == at)?;
+        Some(self.foods.remove(index))
+    }
+
+    /// Refills after a meal, always leaving at least one food on the board.
+    pub fn refill_food(&mut self) {
+        let mut count = self.food_rules.refill_after_eating(self.metrics.food_eaten);
+        if self.foods.is_empty() {
+            count = count.max(1);
+        }
+        self.spawn_food(count);
+    }
+
+    /// Whether the snake covers every cell, leaving nowhere to move or spawn.
+    pub fn board_is_full(&self) -> bool {
+        let cells = (self.board.width.max(0) as usize) * (self.board.height.max(0) as usize);
+        self.snake.len() >= cells
+    }
+
+    /// A filled board without any repositions along the way.
+    pub fn is_perfect(&self) -> bool {
+        self.board_filled && self.metrics.repositions == 0
+    }
+
+    pub fn end_state(&self) -> RunEnd {
+        if !self.ended {
+            RunEnd::Continue
+        } else if self.won {
+            RunEnd::Won
+        } else if self.timed_out {
+            RunEnd::TimeUp
+        } else if self.out_of_moves {
+            RunEnd::OutOfMoves
+        } else {
+            RunEnd::End {
+                show_replay: self.show_replay,
+            }
+        }
+    }
+
+    /// Whether `point` is off the board, inside a wall or on a roamer.
+    pub fn hits_wall(&self, point: Point) -> bool {
+        !point.within(self.board)
+            || self.walls.contains(&point)
+            || self
+                .arena
+                .as_ref()
+                .is_some_and(|arena| arena.has_roamer_at(point))
+    }
+
+    pub fn wraps_walls(&self) -> bool {
+        self.effects.has_soft_wrap
+            || self.difficulty.wraps_walls()
+            || self.custom.as_ref().is_some_and(|rules| rules.wraps)
+    }
+
+    pub fn speed_level(&self) -> u32 {
+        self.speed_curve.level(&self.metrics)
+    }
+
+    /// Simulation speed relative to the base tick rate: the start speed plus
+    /// the curve's ramp, halved by the slow-window tool, times any speed-up
+    /// food boost.
+    pub fn speed_percent(&self) -> u32 {
+        let step = if self.effects.has_slow_window {
+            SPEED_STEP_PERCENT / 2
+        } else {
+            SPEED_STEP_PERCENT
+        };
+        let ramped = self.start_speed_percent + self.speed_level() * step;
+        if self.speed_boost_ticks > 0 {
+            ramped * 140 / 100
+        } else {
+            ramped
+        }
+    }
+
+    fn record_food(&mut self, kind: FoodKind, growth: u64) {
+        self.metrics.food_eaten = self.metrics.food_eaten.saturating_add(1);
+        self.metrics.growth_units = self.metrics.growth_units.saturating_add(growth);
+        *self.metrics.eaten_by_kind.entry(kind).or_default() += 1;
+        if let Some(head) = self.snake.first() {
+            self.events.push(RunEvent::FoodEaten { at: *head, kind });
+        }
+    }
+
+    pub fn drain_events(&mut self) -> Vec<RunEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Abandoned mortal runs are discarded. Runs that only end when the player
+    /// leaves (Invincible) still rank when abandoned.
+    pub fn is_ranked(&self) -> bool {
+        !self.abandoned || self.policy().run_end_state(false) == RunEnd::Continue
+    }
+
+    /// The policy in force: the run's custom rules, or its mode's policy.
+    pub fn policy(&self) -> Box<dyn ModePolicy> {
+        match &self.custom {
+            Some(rules) => Box::new(CustomPolicy {
+                rules: rules.clone(),
+            }),
+            None => self.mode.policy(),
+        }
     }
 
     pub fn runtime_loadout(&self) -> Option<&ToolLoadout> {
@@ -459,29 +1725,98 @@ impl GameRun {
 
 #[derive(Debug, Clone)]
 pub struct GameEngine {
+    /// The active profile: its unlocks, settings and progress apply.
     pub profile: Profile,
+    /// Every other local profile.
+    pub other_profiles: Vec<Profile>,
     pub leaderboards: Leaderboards,
     pub registry: ToolRegistry,
+    pub modes: ModeRegistry,
     pub thresholds: Vec<u64>,
     pub board: Board,
+    /// Per-mode overrides of each policy's default [`FoodRules`].
+    pub food_rules: BTreeMap<GameMode, FoodRules>,
 }
 
 impl GameEngine {
     pub fn new(profile: Profile) -> Self {
+        Self::with_modes(profile, ModeRegistry::builtin())
+    }
+
+    pub fn with_modes(profile: Profile, modes: ModeRegistry) -> Self {
         let registry = ToolRegistry::demo();
         let thresholds = DEFAULT_THRESHOLDS.to_vec();
         let mut profile = migrate_profile(profile).unwrap_or_default();
         profile.apply_threshold_unlocks(&registry, &thresholds);
         Self {
             profile,
+            other_profiles: Vec::new(),
             leaderboards: Leaderboards::default(),
             registry,
+            modes,
             thresholds,
             board: Board {
                 width: 12,
                 height: 12,
             },
+            food_rules: BTreeMap::new(),
+        }
+    }
+
+    /// Names of every local profile, sorted case-insensitively.
+    pub fn profile_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = std::iter::once(&self.profile)
+            .chain(&self.other_profiles)
+            .map(|profile| profile.name.as_str())
+            .collect();
+        names.sort_by_key(|name| name.to_lowercase());
+        names
+    }
+
+    /// Adds a fresh profile named `name` and makes it active.
+    pub fn create_profile(&mut self, name: &str) -> Result<(), String> {
+        let name = validate_profile_name(name)?;
+        if self
+            .profile_names()
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(&name))
+        {
+            return Err(format!("a profile named {name} already exists"));
+        }
+        if self.other_profiles.len() + 1 >= MAX_PROFILES {
+            return Err(format!("at most {MAX_PROFILES} profiles are supported"));
+        }
+        let mut profile = Profile {
+            name,
+            ..Profile::default()
+        };
+        profile.apply_threshold_unlocks(&self.registry, &self.thresholds);
+        let previous = std::mem::replace(&mut self.profile, profile);
+        self.other_profiles.push(previous);
+        Ok(())
+    }
+
+    /// Makes the profile named `name` active, keeping the current one.
+    pub fn switch_profile(&mut self, name: &str) -> Result<(), String> {
+        if self.profile.name == name {
+            return Ok(());
         }
+        let index = self
+            .other_profiles
+            .iter()
+            .position(|profile| profile.name == name)
+            .ok_or_else(|| format!("unknown profile: {name}"))?;
+        let next = self.other_profiles.remove(index);
+        let previous = std::mem::replace(&mut self.profile, next);
+        self.other_profiles.push(previous);
+        Ok(())
+    }
+
+    pub fn food_rules_for(&self, mode: GameMode) -> FoodRules {
+        self.food_rules
+            .get(&mode)
+            .copied()
+            .unwrap_or_else(|| mode.policy().food_rules())
     }
 
     pub fn enable_replay(&mut self, enabled: bool) {
@@ -489,25 +1824,27 @@ impl GameEngine {
     }
 
     pub fn start_run(
-        &self,
+        &mut self,
         mode: GameMode,
         requested_loadout: Option<Vec<String>>,
     ) -> Result<GameRun, String> {
-        let loadout = match mode {
-            GameMode::Experimental => {
-                let slots = requested_loadout.ok_or_else(|| {
-                    "experimental mode requires selecting three unlocked tools".to_string()
-                })?;
-                Some(
-                    self.registry
-                        .validate_loadout(&self.profile.unlocked_tool_ids, &slots)?,
-                )
-            }
-            _ => None,
+        let loadout = if mode.requires_loadout() {
+            let slots = requested_loadout.ok_or_else(|| {
+                format!("{} mode requires selecting three unlocked tools", mode.id())
+            })?;
+            Some(
+                self.registry
+                    .validate_loadout(&self.profile.unlocked_tool_ids, &slots)?,
+            )
+        } else {
+            None
         };
 
         let effects = effects_from_loadout(loadout.as_ref());
+        let difficulty = self.profile.difficulty;
+        self.profile.runs_started += 1;
         Ok(GameRun {
+            run_id: new_run_id(self.profile.runs_started),
             mode,
             board: self.board,
             snake: vec![
@@ -518,12 +1855,99 @@ impl GameEngine {
             metrics: RunMetrics::default(),
             ended: false,
             show_replay: false,
-            grace_ticks_remaining: 3,
+            grace_ticks_remaining: difficulty.initial_grace_ticks(),
             active_loadout: loadout,
             effects,
+            abandoned: false,
+            events: Vec::new(),
+            pending_growth: 0,
+            speed_boost_ticks: 0,
+            foods: Vec::new(),
+            food_rules: self
+                .food_rules_for(mode)
+                .scaled(difficulty.food_density_percent()),
+            food_seed: 0,
+            won: false,
+            board_filled: false,
+            speed_curve: mode.policy().speed_curve(),
+            difficulty,
+            time_remaining_ticks: mode.policy().time_limit_ticks(),
+            timed_out: false,
+            walls: Vec::new(),
+            puzzle: None,
+            out_of_moves: false,
+            campaign_stage: None,
+            start_speed_percent: difficulty.start_speed_percent(),
+            arena: mode
+                .policy()
+                .arena_schedule()
+                .map(|schedule| Arena::new(schedule, 0)),
+            custom: None,
         })
     }
 
+    /// Starts campaign stage `index` with its own board, food and pace.
+    pub fn start_stage(&mut self, index: usize) -> Result<GameRun, String> {
+        let stage = STAGES
+            .get(index)
+            .ok_or_else(|| format!("unknown campaign stage {index}"))?;
+        if !self.profile.campaign_stage_unlocked(index) {
+            return Err(format!("clear the previous stage to unlock {}", stage.name));
+        }
+        let mut run = self.start_run(GameMode::CAMPAIGN, None)?;
+        run.board = stage.board;
+        run.food_rules = stage.food_rules;
+        run.speed_curve = stage.speed_curve;
+        run.start_speed_percent = stage.start_speed_percent;
+        run.difficulty = Difficulty::Normal;
+        run.grace_ticks_remaining = Difficulty::Normal.initial_grace_ticks();
+        run.campaign_stage = Some(index);
+        Ok(run)
+    }
+
+    /// Starts a custom run from `rules`. Every tool it lists must be unlocked.
+    pub fn start_custom(&mut self, rules: &CustomRules) -> Result<GameRun, String> {
+        for tool in &rules.tools {
+            if self.registry.tool(tool).is_none() {
+                return Err(format!("unknown tool: {tool}"));
+            }
+            if !self.profile.unlocked_tool_ids.contains(tool) {
+                return Err(format!("tool not unlocked: {tool}"));
+            }
+        }
+        let mut run = self.start_run(GameMode::CUSTOM, None)?;
+        run.food_rules = rules
+            .food_rules
+            .scaled(run.difficulty.food_density_percent());
+        run.speed_curve = rules.speed_curve;
+        run.effects = effects_from_tools(&rules.tools);
+        run.custom = Some(rules.clone());
+        Ok(run)
+    }
+
+    /// Starts a puzzle run from its fixed layout. Difficulty does not apply.
+    pub fn start_puzzle(&mut self, id: &str) -> Result<GameRun, String> {
+        let puzzle = puzzle::puzzle(id).ok_or_else(|| format!("unknown puzzle {id}"))?;
+        let mut run = self.start_run(GameMode::PUZZLE, None)?;
+        run.board = puzzle.board;
+        run.snake = puzzle.snake.to_vec();
+        run.walls = puzzle.walls.to_vec();
+        run.foods = puzzle
+            .foods
+            .iter()
+            .map(|at| Food::new(FoodKind::Normal, *at, 0))
+            .collect();
+        run.food_rules = FoodRules::fixed();
+        run.start_speed_percent = Difficulty::Normal.start_speed_percent();
+        run.difficulty = Difficulty::Normal;
+        run.grace_ticks_remaining = 0;
+        run.puzzle = Some(ActivePuzzle {
+            puzzle,
+            moves_used: 0,
+        });
+        Ok(run)
+    }
+
     pub fn handle_collision(
         &mut self,
         run: &mut GameRun,
@@ -533,7 +1957,7 @@ impl GameEngine {
             return Err("run has already ended".to_string());
         }
 
-        let policy = policy_for(run.mode);
+        let policy = run.policy();
         match policy.collision_outcome() {
             CollisionOutcome::Die => {
                 run.ended = true;
@@ -541,23 +1965,117 @@ impl GameEngine {
                     policy.run_end_state(self.profile.replay_on_death),
                     RunEnd::End { show_replay: true }
                 );
+                if let Some(head) = run.snake.first() {
+                    run.events.push(RunEvent::Died { at: *head });
+                }
             }
+            CollisionOutcome::Reposition if run.board_is_full() => Self::win_run(run),
             CollisionOutcome::Reposition => {
                 let safe = self.find_safe_respawn(run, candidate_respawn)?;
                 if let Some(head) = run.snake.first_mut() {
-                    *head = safe;
+                    let from = std::mem::replace(head, safe);
+                    run.events.push(RunEvent::Repositioned { from, to: safe });
                 }
-                run.grace_ticks_remaining = 2;
+                run.metrics.repositions = run.metrics.repositions.saturating_add(1);
+                let ticks = run.difficulty.reposition_grace_ticks();
+                run.grace_ticks_remaining = ticks;
+                run.events.push(RunEvent::GraceStarted { ticks });
             }
         }
         Ok(())
     }
 
-    pub fn finish_run(&mut self, run: &GameRun) -> Result<(), String> {
-        let policy = policy_for(run.mode);
-        let score = policy.score(&run.metrics, &run.effects);
+    /// Ends `run` as a win once the board is full. Call after each move.
+    pub fn check_board_full(&mut self, run: &mut GameRun) -> bool {
+        if run.ended || !run.board_is_full() {
+            return false;
+        }
+        Self::win_run(run);
+        true
+    }
+
+    /// Ends a timed run once its clock runs out. Call after each tick.
+    pub fn check_time_up(&mut self, run: &mut GameRun) -> bool {
+        if run.ended || run.time_remaining_ticks != Some(0) {
+            return false;
+        }
+        run.ended = true;
+        run.timed_out = true;
+        run.show_replay = false;
+        run.events.push(RunEvent::TimeUp);
+        true
+    }
+
+    /// Counts a puzzle move and ends the run once the goal is met or the
+    /// budget is spent. Call after each move.
+    pub fn check_puzzle(&mut self, run: &mut GameRun) -> bool {
+        let Some(active) = run.puzzle.as_mut() else {
+            return false;
+        };
+        if run.ended {
+            return false;
+        }
+        active.moves_used += 1;
+        let active = *active;
+        if active.goal_met(&run.snake, run.foods.len()) {
+            run.ended = true;
+            run.won = true;
+            run.show_replay = false;
+            run.events.push(RunEvent::PuzzleSolved {
+                stars: active.puzzle.stars(active.moves_used),
+            });
+        } else if active.moves_remaining() == 0 {
+            run.ended = true;
+            run.out_of_moves = true;
+            run.show_replay = false;
+            run.events.push(RunEvent::OutOfMoves);
+        } else {
+            return false;
+        }
+        true
+    }
 
-        if run.mode == GameMode::Invincible {
+    /// Ends a campaign run once its stage goal is met. Call after each move.
+    pub fn check_stage(&mut self, run: &mut GameRun) -> bool {
+        let Some(index) = run.campaign_stage else {
+            return false;
+        };
+        if run.ended || !STAGES[index].goal_met(&run.metrics, run.snake.len()) {
+            return false;
+        }
+        run.ended = true;
+        run.won = true;
+        run.show_replay = false;
+        run.events.push(RunEvent::StageCleared { stage: index });
+        true
+    }
+
+    fn win_run(run: &mut GameRun) {
+        run.ended = true;
+        run.won = true;
+        run.board_filled = true;
+        run.show_replay = false;
+        run.events.push(RunEvent::BoardFilled {
+            length: run.snake.len(),
+        });
+    }
+
+    /// Records the run's progress and, if it is ranked, submits it. Returns
+    /// the submitted entry's run id.
+    pub fn finish_run(&mut self, run: &GameRun) -> Result<Option<u64>, String> {
+        self.finish_run_at(run, unix_now())
+    }
+
+    /// [`Self::finish_run`] with the leaderboard timestamp given explicitly.
+    pub fn finish_run_at(
+        &mut self,
+        run: &GameRun,
+        recorded_at: u64,
+    ) -> Result<Option<u64>, String> {
+        let policy = run.policy();
+        let score = policy.final_score(run);
+
+        if run.mode == GameMode::INVINCIBLE {
             self.profile.invincible_cumulative_length = self
                 .profile
                 .invincible_cumulative_length
@@ -566,19 +2084,81 @@ impl GameEngine {
                 .apply_threshold_unlocks(&self.registry, &self.thresholds);
         }
 
-        let loadout_summary = run
-            .active_loadout
-            .as_ref()
-            .map(ToolLoadout::summary)
-            .unwrap_or_else(|| "none".to_string());
+        if let Some(index) = run.campaign_stage.filter(|_| run.won) {
+            self.profile
+                .campaign_cleared
+                .insert(STAGES[index].id.to_string());
+        }
+
+        if let Some(active) = run.puzzle.filter(|_| run.won) {
+            let stars = active.puzzle.stars(active.moves_used);
+            let best = self
+                .profile
+                .puzzle_stars
+                .entry(active.puzzle.id.to_string())
+                .or_default();
+            *best = (*best).max(stars);
+        }
+
+        if !run.is_ranked() {
+            return Ok(None);
+        }
+
+        let run_id = run.run_id;
+        let loadout_summary = match (&run.active_loadout, &run.custom) {
+            (Some(loadout), _) => loadout.summary(),
+            (None, Some(rules)) => rules.tools_summary(),
+            (None, None) => "none".to_string(),
+        };
 
         self.leaderboards.submit(LeaderboardEntry {
             mode: run.mode,
             score,
             survival_ticks: run.metrics.survival_ticks,
             loadout_summary,
+            perfect: run.is_perfect(),
+            difficulty: run.difficulty,
+            preset: run.custom.as_ref().map(|rules| rules.name.clone()),
+            food_eaten: run.metrics.food_eaten,
+            growth_units: run.metrics.growth_units,
+            recorded_at,
+            board: run.board,
+            run_id,
+            player: self.profile.name.clone(),
         });
-        Ok(())
+        Ok(Some(run_id))
+    }
+
+    pub fn export_leaderboard(&self, mode: GameMode, format: ExportFormat) -> String {
+        leaderboard_io::export(&self.leaderboards, mode, format)
+    }
+
+    /// Merges exported rows into the leaderboards. Rows whose run is already
+    /// on a board are skipped and rows that fail validation are reported;
+    /// only a malformed file is an error.
+    pub fn import_leaderboard(
+        &mut self,
+        text: &str,
+        format: ExportFormat,
+    ) -> Result<ImportReport, String> {
+        let mut report = ImportReport::default();
+        for (index, read) in leaderboard_io::read_entries(text, format, &self.modes)?
+            .into_iter()
+            .enumerate()
+        {
+            let checked = read.and_then(|entry| {
+                leaderboard_io::check_entry(&entry, &self.registry).map(|()| entry)
+            });
+            match checked {
+                Ok(entry) if self.leaderboards.contains_run(entry.run_id) => report.duplicates += 1,
+                Ok(entry) => {
+                    self.leaderboards.submit(entry);
+                    report.added += 1;
+                }
+                Err(err) => report.rejected.push(format!("row {}: {err}", index + 1)),
+            }
+        }
+        Ok(report)
     }
 
     fn find_safe_respawn(&self, run: &GameRun, desired: Point) -> Result<Point, String> {
@@ -598,15 +2178,13 @@ impl GameEngine {
         Err("no safe respawn tile found".to_string())
     }
 
-    pub fn build_menu_items(&self) -> Vec<&'static str> {
-        vec![
-            "Start Practice",
-            "Start Challenge",
-            "Start Experimental",
-            "Start Invincible",
-            "Leaderboards",
-            "Settings",
-        ]
+    pub fn build_menu_items(&self) -> Vec<String> {
+        self.modes
+            .list()
+            .iter()
+            .map(|mode| format!("Start {}", mode.label()))
+            .chain(["Leaderboards".to_string(), "Settings".to_string()])
+            .collect()
     }
 }
 
@@ -615,8 +2193,10 @@ mod tests {
     use super::*;
 
     fn unlocked_profile() -> Profile {
-        let mut profile = Profile::default();
-        profile.invincible_cumulative_length = 200;
+        let mut profile = Profile {
+            invincible_cumulative_length: 200,
+            ..Profile::default()
+        };
         let registry = ToolRegistry::demo();
         profile.apply_threshold_unlocks(&registry, &DEFAULT_THRESHOLDS);
         profile
@@ -625,19 +2205,19 @@ mod tests {
     #[test]
     fn policy_collision_outcomes_are_mode_scoped() {
         assert_eq!(
-            policy_for(GameMode::Practice).collision_outcome(),
+            GameMode::PRACTICE.policy().collision_outcome(),
             CollisionOutcome::Die
         );
         assert_eq!(
-            policy_for(GameMode::Challenge).collision_outcome(),
+            GameMode::CHALLENGE.policy().collision_outcome(),
             CollisionOutcome::Die
         );
         assert_eq!(
-            policy_for(GameMode::Experimental).collision_outcome(),
+            GameMode::EXPERIMENTAL.policy().collision_outcome(),
             CollisionOutcome::Die
         );
         assert_eq!(
-            policy_for(GameMode::Invincible).collision_outcome(),
+            GameMode::INVINCIBLE.policy().collision_outcome(),
             CollisionOutcome::Reposition
         );
     }
@@ -646,7 +2226,7 @@ mod tests {
     fn mortal_collision_ends_immediately_with_optional_replay() {
         let mut engine = GameEngine::new(Profile::default());
         engine.enable_replay(true);
-        let mut run = engine.start_run(GameMode::Practice, None).unwrap();
+        let mut run = engine.start_run(GameMode::PRACTICE, None).unwrap();
 
         engine
             .handle_collision(&mut run, Point { x: 0, y: 0 })
@@ -659,7 +2239,7 @@ mod tests {
     #[test]
     fn invincible_collision_repositions_without_ending() {
         let mut engine = GameEngine::new(Profile::default());
-        let mut run = engine.start_run(GameMode::Invincible, None).unwrap();
+        let mut run = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
         let body = run.snake.clone();
 
         engine
@@ -672,15 +2252,15 @@ mod tests {
 
     #[test]
     fn new_run_starts_with_three_grace_ticks() {
-        let engine = GameEngine::new(Profile::default());
-        let run = engine.start_run(GameMode::Practice, None).unwrap();
+        let mut engine = GameEngine::new(Profile::default());
+        let run = engine.start_run(GameMode::PRACTICE, None).unwrap();
         assert_eq!(run.grace_ticks_remaining, 3);
     }
 
     #[test]
     fn grace_ticks_decrement_on_each_tick() {
-        let engine = GameEngine::new(Profile::default());
-        let mut run = engine.start_run(GameMode::Practice, None).unwrap();
+        let mut engine = GameEngine::new(Profile::default());
+        let mut run = engine.start_run(GameMode::PRACTICE, None).unwrap();
         assert_eq!(run.grace_ticks_remaining, 3);
 
         run.tick();
@@ -699,7 +2279,7 @@ mod tests {
     #[test]
     fn collision_grace_period_does_not_stack() {
         let mut engine = GameEngine::new(Profile::default());
-        let mut run = engine.start_run(GameMode::Invincible, None).unwrap();
+        let mut run = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
         assert_eq!(run.grace_ticks_remaining, 3);
 
         run.tick();
@@ -718,17 +2298,17 @@ mod tests {
     fn challenge_leaderboard_orders_by_survival_time_first() {
         let mut engine = GameEngine::new(Profile::default());
 
-        let mut short = engine.start_run(GameMode::Challenge, None).unwrap();
+        let mut short = engine.start_run(GameMode::CHALLENGE, None).unwrap();
         short.metrics.survival_ticks = 30;
         short.metrics.food_eaten = 100;
         engine.finish_run(&short).unwrap();
 
-        let mut long = engine.start_run(GameMode::Challenge, None).unwrap();
+        let mut long = engine.start_run(GameMode::CHALLENGE, None).unwrap();
         long.metrics.survival_ticks = 60;
         long.metrics.food_eaten = 1;
         engine.finish_run(&long).unwrap();
 
-        let rows = engine.leaderboards.rows(GameMode::Challenge);
+        let rows = engine.leaderboards.rows(GameMode::CHALLENGE);
         assert_eq!(rows[0].survival_ticks, 60);
     }
 
@@ -736,28 +2316,28 @@ mod tests {
     fn invincible_scores_are_isolated_from_other_modes() {
         let mut engine = GameEngine::new(Profile::default());
 
-        let mut invincible = engine.start_run(GameMode::Invincible, None).unwrap();
+        let mut invincible = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
         invincible.metrics.food_eaten = 10;
         engine.finish_run(&invincible).unwrap();
 
-        let practice = engine.start_run(GameMode::Practice, None).unwrap();
+        let practice = engine.start_run(GameMode::PRACTICE, None).unwrap();
         engine.finish_run(&practice).unwrap();
 
-        assert_eq!(engine.leaderboards.rows(GameMode::Invincible).len(), 1);
-        assert_eq!(engine.leaderboards.rows(GameMode::Practice).len(), 1);
-        assert_eq!(engine.leaderboards.rows(GameMode::Challenge).len(), 0);
+        assert_eq!(engine.leaderboards.rows(GameMode::INVINCIBLE).len(), 1);
+        assert_eq!(engine.leaderboards.rows(GameMode::PRACTICE).len(), 1);
+        assert_eq!(engine.leaderboards.rows(GameMode::CHALLENGE).len(), 0);
     }
 
     #[test]
     fn invincible_growth_updates_cumulative_length_only_for_invincible() {
         let mut engine = GameEngine::new(Profile::default());
 
-        let mut invincible = engine.start_run(GameMode::Invincible, None).unwrap();
+        let mut invincible = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
         invincible.add_food(20);
         engine.finish_run(&invincible).unwrap();
         assert_eq!(engine.profile.invincible_cumulative_length, 20);
 
-        let mut practice = engine.start_run(GameMode::Practice, None).unwrap();
+        let mut practice = engine.start_run(GameMode::PRACTICE, None).unwrap();
         practice.add_food(99);
         engine.finish_run(&practice).unwrap();
         assert_eq!(engine.profile.invincible_cumulative_length, 20);
@@ -801,11 +2381,30 @@ mod tests {
         assert!(err.is_err());
     }
 
+    #[test]
+    fn pointer_control_defaults_to_hover_and_persists_per_mode() {
+        let mut profile = Profile::default();
+        assert_eq!(
+            profile.pointer_control(GameMode::CHALLENGE),
+            PointerControl::HoverSteer
+        );
+
+        profile.set_pointer_control(GameMode::CHALLENGE, PointerControl::Off);
+        assert_eq!(
+            profile.pointer_control(GameMode::CHALLENGE),
+            PointerControl::Off
+        );
+        assert_eq!(
+            profile.pointer_control(GameMode::PRACTICE),
+            PointerControl::HoverSteer
+        );
+    }
+
     #[test]
     fn experimental_loadout_requires_three_unlocked_tools() {
-        let engine = GameEngine::new(unlocked_profile());
+        let mut engine = GameEngine::new(unlocked_profile());
         let ok = engine.start_run(
-            GameMode::Experimental,
+            GameMode::EXPERIMENTAL,
             Some(vec![
                 "turn-buffer".to_string(),
                 "slow-window".to_string(),
@@ -815,7 +2414,7 @@ mod tests {
         assert!(ok.is_ok());
 
         let bad_len = engine.start_run(
-            GameMode::Experimental,
+            GameMode::EXPERIMENTAL,
             Some(vec!["turn-buffer".to_string()]),
         );
         assert!(bad_len.is_err());
@@ -823,9 +2422,9 @@ mod tests {
 
     #[test]
     fn locked_tools_cannot_be_equipped() {
-        let engine = GameEngine::new(Profile::default());
+        let mut engine = GameEngine::new(Profile::default());
         let run = engine.start_run(
-            GameMode::Experimental,
+            GameMode::EXPERIMENTAL,
             Some(vec![
                 "turn-buffer".to_string(),
                 "slow-window".to_string(),
@@ -837,10 +2436,10 @@ mod tests {
 
     #[test]
     fn active_loadout_is_snapshot_and_immutable_during_run() {
-        let engine = GameEngine::new(unlocked_profile());
+        let mut engine = GameEngine::new(unlocked_profile());
         let mut run = engine
             .start_run(
-                GameMode::Experimental,
+                GameMode::EXPERIMENTAL,
                 Some(vec![
                     "turn-buffer".to_string(),
                     "slow-window".to_string(),
@@ -864,16 +2463,647 @@ mod tests {
         assert!(change_result.is_err());
     }
 
+    #[test]
+    fn abandoned_mortal_runs_are_not_submitted() {
+        let mut engine = GameEngine::new(Profile::default());
+
+        let mut practice = engine.start_run(GameMode::PRACTICE, None).unwrap();
+        practice.metrics.food_eaten = 4;
+        practice.abandoned = true;
+        engine.finish_run(&practice).unwrap();
+        assert!(engine.leaderboards.rows(GameMode::PRACTICE).is_empty());
+
+        let mut invincible = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
+        invincible.add_food(3);
+        invincible.abandoned = true;
+        engine.finish_run(&invincible).unwrap();
+        assert_eq!(engine.leaderboards.rows(GameMode::INVINCIBLE).len(), 1);
+        assert_eq!(engine.profile.invincible_cumulative_length, 3);
+    }
+
+    #[test]
+    fn collisions_and_food_queue_run_events() {
+        let mut engine = GameEngine::new(Profile::default());
+        let mut run = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
+        run.add_food(1);
+        engine
+            .handle_collision(&mut run, Point { x: 9, y: 9 })
+            .unwrap();
+
+        let head = Point { x: 5, y: 5 };
+        assert_eq!(
+            run.drain_events(),
+            vec![
+                RunEvent::FoodEaten {
+                    at: head,
+                    kind: FoodKind::Normal,
+                },
+                RunEvent::Repositioned {
+                    from: head,
+                    to: Point { x: 9, y: 9 },
+                },
+                RunEvent::GraceStarted { ticks: 2 },
+            ]
+        );
+        assert!(run.events.is_empty());
+        assert_eq!(run.metrics.repositions, 1);
+
+        let mut practice = engine.start_run(GameMode::PRACTICE, None).unwrap();
+        engine
+            .handle_collision(&mut practice, Point { x: 0, y: 0 })
+            .unwrap();
+        assert_eq!(practice.drain_events(), vec![RunEvent::Died { at: head }]);
+    }
+
+    #[test]
+    fn food_kinds_change_growth_speed_and_mode_weighted_score() {
+        let mut engine = GameEngine::new(Profile::default());
+        let mut run = engine.start_run(GameMode::PRACTICE, None).unwrap();
+        run.eat_food(FoodKind::Golden);
+        assert_eq!(run.metrics.growth_units, 3);
+        for _ in 0..3 {
+            run.snake.insert(0, Point { x: 6, y: 5 });
+            run.trim_tail();
+        }
+        assert_eq!(run.snake.len(), 6);
+        run.trim_tail();
+        assert_eq!(run.snake.len(), 5);
+
+        run.eat_food(FoodKind::Shrinking);
+        run.trim_tail();
+        assert_eq!(run.snake.len(), 2);
+
+        run.eat_food(FoodKind::SpeedUp);
+        assert_eq!(run.speed_percent(), 140);
+        for _ in 0..food::SPEED_UP_TICKS {
+            run.tick();
+        }
+        assert_eq!(run.speed_percent(), 100);
+
+        let practice = PracticePolicy.score(&run.metrics, &run.effects);
+        assert_eq!(practice, 3 * 10 + 30 + 5 + 10);
+        let mut challenge_metrics = run.metrics.clone();
+        challenge_metrics.survival_ticks = 0;
+        assert_eq!(
+            ChallengePolicy.score(&challenge_metrics, &ActiveEffects::default()),
+            3 * 10 + 20 + 40
+        );
+    }
+
+    #[test]
+    fn food_rules_come_from_policy_unless_engine_overrides() {
+        let mut engine = GameEngine::new(Profile::default());
+        let mut challenge = engine.start_run(GameMode::CHALLENGE, None).unwrap();
+        challenge.seed_food(3);
+        assert_eq!(challenge.foods.len(), 1);
+        assert_eq!(challenge.foods[0].kind, FoodKind::Normal);
+
+        engine
+            .food_rules
+            .insert(GameMode::CHALLENGE, FoodRules::cluster());
+        let mut clustered = engine.start_run(GameMode::CHALLENGE, None).unwrap();
+        clustered.seed_food(3);
+        assert_eq!(clustered.foods.len(), 4);
+        for (index, food) in clustered.foods.iter().enumerate().skip(1) {
+            assert!(clustered.foods[..index].iter().any(|earlier| {
+                let (a, b) = (earlier.position, food.position);
+                (a.x - b.x).abs() + (a.y - b.y).abs() == 1
+            }));
+        }
+    }
+
+    #[test]
+    fn timed_waves_spawn_on_schedule_and_keep_one_food_after_meals() {
+        let mut engine = GameEngine::new(Profile::default());
+        let mut run = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
+        run.seed_food(11);
+        assert_eq!(run.foods.len(), 3);
+
+        run.metrics.survival_ticks = 30;
+        run.update_food();
+        assert_eq!(run.foods.len(), 7);
+
+        run.foods.clear();
+        run.eat_food(FoodKind::Normal);
+        run.refill_food();
+        assert_eq!(run.foods.len(), 1);
+    }
+
+    #[test]
+    fn spawning_on_a_full_board_reports_it() {
+        let mut engine = GameEngine::new(Profile::default());
+        let mut run = engine.start_run(GameMode::PRACTICE, None).unwrap();
+        run.board = Board {
+            width: 2,
+            height: 2,
+        };
+        run.snake = vec![
+            Point { x: 0, y: 0 },
+            Point { x: 1, y: 0 },
+            Point { x: 1, y: 1 },
+        ];
+        assert_eq!(run.spawn_food(2), Some(FoodPlacement::BoardFull));
+        assert_eq!(run.foods.len(), 1);
+        assert_eq!(run.foods[0].position, Point { x: 0, y: 1 });
+    }
+
+    #[test]
+    fn speed_curves_ramp_per_mode_and_slow_window_halves_the_step() {
+        let mut engine = GameEngine::new(unlocked_profile());
+        let mut practice = engine.start_run(GameMode::PRACTICE, None).unwrap();
+        practice.metrics.survival_ticks = 5_000;
+        assert_eq!(practice.speed_level(), 0);
+        assert_eq!(practice.speed_percent(), 100);
+
+        let mut challenge = engine.start_run(GameMode::CHALLENGE, None).unwrap();
+        challenge.metrics.survival_ticks = 299;
+        challenge.tick();
+        assert_eq!(challenge.speed_level(), 3);
+        assert_eq!(challenge.speed_percent(), 124);
+        assert_eq!(challenge.metrics.speed_level_ticks, 3);
+        challenge.metrics.survival_ticks = 50_000;
+        assert_eq!(challenge.speed_level(), 10);
+
+        let mut slowed = engine
+            .start_run(
+                GameMode::EXPERIMENTAL,
+                Some(vec![
+                    "turn-buffer".to_string(),
+                    "slow-window".to_string(),
+                    "soft-wrap".to_string(),
+                ]),
+            )
+            .unwrap();
+        slowed.metrics.growth_units = 10;
+        assert_eq!(slowed.speed_level(), 2);
+        assert_eq!(slowed.speed_percent(), 108);
+        slowed.speed_boost_ticks = 1;
+        assert_eq!(slowed.speed_percent(), 151);
+    }
+
+    #[test]
+    fn time_at_speed_adds_to_the_score() {
+        let policy = ExperimentalPolicy;
+        let metrics = RunMetrics {
+            speed_level_ticks: 250,
+            ..RunMetrics::default()
+        };
+        assert_eq!(policy.score(&metrics, &ActiveEffects::default()), 25);
+    }
+
+    #[test]
+    fn difficulty_tunes_runs_and_is_recorded_on_entries() {
+        let mut engine = GameEngine::new(Profile {
+            difficulty: Difficulty::Easy,
+            ..Profile::default()
+        });
+        let mut easy = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
+        assert_eq!(easy.grace_ticks_remaining, 5);
+        assert_eq!(easy.speed_percent(), 80);
+        assert!(easy.wraps_walls());
+        assert_eq!(easy.food_rules.initial_count, 5);
+        easy.grace_ticks_remaining = 0;
+        engine
+            .handle_collision(&mut easy, Point { x: 0, y: 0 })
+            .unwrap();
+        assert_eq!(easy.grace_ticks_remaining, 3);
+        engine.finish_run(&easy).unwrap();
+        let classic = engine.start_run(GameMode::CHALLENGE, None).unwrap();
+        assert_eq!(classic.food_rules, FoodRules::single_apple());
+
+        engine.profile.difficulty = Difficulty::Insane;
+        let insane = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
+        assert_eq!(insane.grace_ticks_remaining, 1);
+        assert_eq!(insane.speed_percent(), 145);
+        assert!(!insane.wraps_walls());
+        assert_eq!(insane.food_rules.initial_count, 2);
+        engine.finish_run(&insane).unwrap();
+
+        let insane_only = LeaderboardFilter {
+            difficulty: Some(Difficulty::Insane),
+            ..Default::default()
+        };
+        let insane_rows: Vec<_> = engine
+            .leaderboards
+            .rows_matching(GameMode::INVINCIBLE, &insane_only)
+            .collect();
+        assert_eq!(insane_rows.len(), 1);
+        assert_eq!(insane_rows[0].difficulty, Difficulty::Insane);
+        assert_eq!(
+            engine
+                .leaderboards
+                .rows_matching(GameMode::INVINCIBLE, &LeaderboardFilter::default())
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn time_attack_clock_counts_down_extends_on_food_and_ends_the_run() {
+        let mut engine = GameEngine::new(Profile::default());
+        let mut run = engine.start_run(GameMode::TIME_ATTACK, None).unwrap();
+        assert_eq!(run.time_remaining_ticks, Some(TIME_ATTACK_BUDGET_TICKS));
+        run.tick();
+        run.eat_food(FoodKind::Golden);
+        assert_eq!(
+            run.time_remaining_ticks,
+            Some(TIME_ATTACK_BUDGET_TICKS - 1 + 40)
+        );
+        assert!(!engine.check_time_up(&mut run));
+
+        run.time_remaining_ticks = Some(1);
+        run.tick();
+        assert!(engine.check_time_up(&mut run));
+        assert_eq!(run.end_state(), RunEnd::TimeUp);
+        assert!(run.drain_events().contains(&RunEvent::TimeUp));
+        assert_eq!(
+            TimeAttackPolicy.score(&run.metrics, &run.effects),
+            20 + TimeAttackPolicy.food_bonus(FoodKind::Golden)
+        );
+
+        let practice = engine.start_run(GameMode::PRACTICE, None).unwrap();
+        assert_eq!(practice.time_remaining_ticks, None);
+    }
+
+    #[test]
+    fn time_attack_ties_rank_the_quicker_run_first() {
+        let mut boards = Leaderboards::default();
+        for survival_ticks in [700, 500] {
+            boards.submit(LeaderboardEntry {
+                mode: GameMode::TIME_ATTACK,
+                score: 200,
+                survival_ticks,
+                loadout_summary: "none".to_string(),
+                perfect: false,
+                difficulty: Difficulty::Normal,
+                preset: None,
+                food_eaten: 10,
+                growth_units: 10,
+                recorded_at: 0,
+                board: Board {
+                    width: 12,
+                    height: 12,
+                },
+                run_id: survival_ticks,
+                player: DEFAULT_PROFILE_NAME.to_string(),
+            });
+        }
+        let rows = boards.rows(GameMode::TIME_ATTACK);
+        assert_eq!(rows[0].survival_ticks, 500);
+        assert_eq!(rows[1].survival_ticks, 700);
+    }
+
+    #[test]
+    fn named_profiles_keep_their_own_progress_and_sign_their_runs() {
+        let mut engine = GameEngine::new(Profile::default());
+        assert!(validate_profile_name("  ").is_err());
+        assert!(validate_profile_name("a/b").is_err());
+        assert!(validate_profile_name("seventeen letters").is_err());
+        assert_eq!(validate_profile_name(" Mo ").unwrap(), "Mo");
+
+        engine.profile.difficulty = Difficulty::Hard;
+        engine.profile.campaign_cleared.insert("meadow".to_string());
+        engine.create_profile("mo").unwrap();
+        assert!(engine.create_profile("MO").is_err());
+        assert!(engine.create_profile("player").is_err());
+        assert_eq!(engine.profile.name, "mo");
+        assert_eq!(engine.profile.difficulty, Difficulty::Normal);
+        assert!(engine.profile.campaign_cleared.is_empty());
+        assert_eq!(engine.profile_names(), ["mo", DEFAULT_PROFILE_NAME]);
+
+        let mut run = engine.start_run(GameMode::PRACTICE, None).unwrap();
+        run.abandoned = false;
+        engine.finish_run(&run).unwrap();
+        assert_eq!(engine.leaderboards.rows(GameMode::PRACTICE)[0].player, "mo");
+
+        engine.switch_profile(DEFAULT_PROFILE_NAME).unwrap();
+        assert_eq!(engine.profile.difficulty, Difficulty::Hard);
+        assert!(engine.profile.campaign_cleared.contains("meadow"));
+        assert!(engine.switch_profile("nobody").is_err());
+        for name in ["c", "d", "e", "f", "g", "h"] {
+            engine.create_profile(name).unwrap();
+        }
+        assert!(engine.create_profile("i").is_err());
+    }
+
+    #[test]
+    fn imports_merge_other_machines_once_and_report_bad_rows() {
+        let mut home = GameEngine::new(Profile::default());
+        let mut away = GameEngine::new(Profile::default());
+        fn play(engine: &mut GameEngine, ticks: u64) {
+            let mut run = engine.start_run(GameMode::CHALLENGE, None).unwrap();
+            run.metrics.survival_ticks = ticks;
+            run.abandoned = false;
+            engine.finish_run_at(&run, 1_000 + ticks).unwrap();
+        }
+        play(&mut home, 30);
+        play(&mut away, 50);
+        // Same seed, second and outcome: still two runs with their own ids.
+        play(&mut away, 70);
+        play(&mut away, 70);
+        assert_eq!(away.profile.runs_started, 3);
+
+        for format in [ExportFormat::Json, ExportFormat::Csv] {
+            let exported = away.export_leaderboard(GameMode::CHALLENGE, format);
+            let report = home.import_leaderboard(&exported, format).unwrap();
+            let added = if format == ExportFormat::Json { 3 } else { 0 };
+            assert_eq!((report.added, report.duplicates), (added, 3 - added));
+        }
+        let ticks: Vec<u64> = home
+            .leaderboards
+            .rows(GameMode::CHALLENGE)
+            .iter()
+            .map(|row| row.survival_ticks)
+            .collect();
+        assert_eq!(ticks, [70, 70, 50, 30]);
+
+        let mut forged = away.export_leaderboard(GameMode::CHALLENGE, ExportFormat::Csv);
+        forged.push_str("99,Player,challenge,1,5,0,0,none,,false,normal,12,12,0\n");
+        let report = home.import_leaderboard(&forged, ExportFormat::Csv).unwrap();
+        assert_eq!(report.added, 0);
+        assert_eq!(report.duplicates, 3);
+        assert_eq!(report.rejected.len(), 1);
+        assert!(report.rejected[0].starts_with("row 4: score 1 is below"));
+        assert!(home.import_leaderboard("{}", ExportFormat::Json).is_err());
+    }
+
+    #[test]
+    fn rankings_apply_keys_in_order_then_favour_the_earlier_run() {
+        let ranking = Ranking::by(RankKey::highest(RankField::Score))
+            .then(RankKey::highest(RankField::Growth))
+            .then(RankKey::lowest(RankField::Food))
+            .build();
+        assert_eq!(ranking.keys().count(), 3);
+        assert_eq!(ranking.tertiary, Some(RankKey::lowest(RankField::Food)));
+
+        let mut engine = GameEngine::new(Profile::default());
+        // (food, growth, finished at): equal scores, so growth decides first.
+        for (food, growth, recorded_at) in [(3, 2, 30), (3, 5, 40), (3, 5, 10), (3, 5, 20)] {
+            let mut run = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
+            run.metrics.food_eaten = food;
+            run.metrics.growth_units = growth;
+            run.abandoned = true;
+            engine.finish_run_at(&run, recorded_at).unwrap();
+        }
+        let order: Vec<(u64, u64)> = engine
+            .leaderboards
+            .rows(GameMode::INVINCIBLE)
+            .iter()
+            .map(|row| (row.growth_units, row.recorded_at))
+            .collect();
+        assert_eq!(order, [(5, 10), (5, 20), (5, 40), (2, 30)]);
+
+        let practice = GameMode::PRACTICE.policy().ranking();
+        assert_eq!(practice.secondary, Some(RankKey::highest(RankField::Food)));
+        assert_eq!(
+            GameMode::ARENA.policy().ranking().primary,
+            RankKey::highest(RankField::Survival)
+        );
+    }
+
+    #[test]
+    fn puzzle_runs_track_moves_and_record_best_stars() {
+        let mut engine = GameEngine::new(Profile {
+            difficulty: Difficulty::Insane,
+            ..Profile::default()
+        });
+        assert!(engine.start_puzzle("missing").is_err());
+
+        let mut run = engine.start_puzzle("first-bite").unwrap();
+        assert_eq!(run.board, PUZZLES[0].board);
+        assert_eq!(
+            run.start_speed_percent,
+            Difficulty::Normal.start_speed_percent()
+        );
+        assert_eq!(run.foods.len(), 2);
+        run.seed_food(9);
+        assert_eq!(run.foods.len(), 2);
+
+        run.foods.clear();
+        run.puzzle.as_mut().unwrap().moves_used = 8;
+        assert!(engine.check_puzzle(&mut run));
+        assert!(run.won && !run.board_filled);
+        assert_eq!(
+            run.drain_events(),
+            vec![RunEvent::PuzzleSolved { stars: 2 }]
+        );
+        engine.finish_run(&run).unwrap();
+        assert_eq!(engine.profile.puzzle_stars("first-bite"), 2);
+        let row = &engine.leaderboards.rows(GameMode::PUZZLE)[0];
+        assert_eq!(row.score, 2 * 100 + 3 * 10);
+        assert!(!row.perfect);
+
+        let mut stuck = engine.start_puzzle("first-bite").unwrap();
+        stuck.puzzle.as_mut().unwrap().moves_used = 11;
+        assert!(engine.check_puzzle(&mut stuck));
+        assert_eq!(stuck.end_state(), RunEnd::OutOfMoves);
+        engine.finish_run(&stuck).unwrap();
+        assert_eq!(engine.profile.puzzle_stars("first-bite"), 2);
+    }
+
+    #[test]
+    fn campaign_stages_unlock_in_order_and_migration_seeds_veterans() {
+        let mut engine = GameEngine::new(Profile::default());
+        assert!(engine.start_stage(1).is_err());
+        assert!(engine.start_stage(STAGES.len()).is_err());
+
+        let mut run = engine.start_stage(0).unwrap();
+        assert_eq!(run.board, STAGES[0].board);
+        assert_eq!(run.start_speed_percent, STAGES[0].start_speed_percent);
+        assert!(!engine.check_stage(&mut run));
+        run.metrics.food_eaten = 5;
+        assert!(engine.check_stage(&mut run));
+        assert!(run.won);
+        assert_eq!(
+            run.drain_events(),
+            vec![RunEvent::StageCleared { stage: 0 }]
+        );
+        engine.finish_run(&run).unwrap();
+        let row = &engine.leaderboards.rows(GameMode::CAMPAIGN)[0];
+        assert_eq!(row.score, 5 * 10);
+        assert!(!row.perfect);
+        assert!(engine.profile.campaign_stage_unlocked(1));
+        assert!(!engine.profile.campaign_stage_unlocked(2));
+        assert!(engine.start_stage(1).is_ok());
+
+        // Schema-2 saves keep the cumulative length but no derived unlocks.
+        let veteran = GameEngine::new(Profile {
+            schema_version: 2,
+            invincible_cumulative_length: DEFAULT_THRESHOLDS[0],
+            ..Profile::default()
+        });
+        assert_eq!(veteran.profile.schema_version, 3);
+        assert!(veteran.profile.campaign_stage_unlocked(1));
+        let newcomer = migrate_profile(Profile {
+            schema_version: 2,
+            ..Profile::default()
+        })
+        .unwrap();
+        assert!(!newcomer.campaign_stage_unlocked(1));
+    }
+
+    #[test]
+    fn arena_adds_hazards_on_schedule_and_ranks_by_survival() {
+        let mut engine = GameEngine::new(Profile::default());
+        let mut run = engine.start_run(GameMode::ARENA, None).unwrap();
+        run.seed_food(11);
+        for _ in 0..ARENA_SCHEDULE.shrink_every_ticks {
+            run.tick();
+            run.update_arena();
+        }
+        let arena = run.arena.clone().unwrap();
+        assert_eq!(arena.inset, 1);
+        let raised = (ARENA_SCHEDULE.shrink_every_ticks / ARENA_SCHEDULE.wall_every_ticks) as u32;
+        assert_eq!(arena.level, raised + 2);
+        assert!(run.hits_wall(Point { x: 0, y: 0 }));
+        // A roamer caught on the closing ring is crushed by it.
+        assert!(
+            arena
+                .roamers
+                .iter()
+                .all(|roamer| run.hits_wall(roamer.position))
+        );
+        assert!(run.walls.iter().all(|wall| !run.snake.contains(wall)
+            && !run.foods.iter().any(|food| food.position == *wall)));
+        let events = run.drain_events();
+        assert!(events.contains(&RunEvent::BorderClosed { inset: 1 }));
+        assert_eq!(
+            events
+                .iter()
+                .filter(|event| matches!(event, RunEvent::HazardAppeared { .. }))
+                .count() as u32,
+            raised + 1
+        );
+
+        run.foods.clear();
+        run.spawn_food(5);
+        assert!(run.foods.iter().all(|food| !run.hits_wall(food.position)));
+
+        engine.finish_run(&run).unwrap();
+        let mut short = engine.start_run(GameMode::ARENA, None).unwrap();
+        short.metrics.survival_ticks = 10;
+        short.metrics.food_eaten = 50;
+        engine.finish_run(&short).unwrap();
+        assert_eq!(
+            engine.leaderboards.rows(GameMode::ARENA)[0].survival_ticks,
+            ARENA_SCHEDULE.shrink_every_ticks
+        );
+    }
+
+    #[test]
+    fn custom_rules_drive_the_run_and_rank_on_their_own_board() {
+        let mut engine = GameEngine::new(Profile::default());
+        let mut rules = CustomRules {
+            name: "Loose".to_string(),
+            collision: CollisionOutcome::Reposition,
+            wraps: true,
+            weights: ScoreWeights {
+                per_food: 7,
+                per_ten_ticks: 3,
+                per_speed_point: 0,
+            },
+            food_rules: FOOD_OPTIONS[2].1,
+            speed_curve: SPEED_OPTIONS[2].1,
+            tools: BTreeSet::from(["soft-wrap".to_string()]),
+        };
+        assert!(engine.start_custom(&rules).is_err());
+        engine
+            .profile
+            .unlocked_tool_ids
+            .insert("soft-wrap".to_string());
+
+        let mut run = engine.start_custom(&rules).unwrap();
+        assert!(run.wraps_walls() && run.effects.has_soft_wrap);
+        assert_eq!(run.food_rules.pattern, FOOD_OPTIONS[2].1.pattern);
+        assert_eq!(run.speed_curve, SPEED_OPTIONS[2].1);
+        run.grace_ticks_remaining = 0;
+        engine
+            .handle_collision(&mut run, Point { x: 1, y: 1 })
+            .unwrap();
+        assert!(!run.ended);
+        assert_eq!(run.metrics.repositions, 1);
+
+        run.metrics.food_eaten = 2;
+        run.metrics.survival_ticks = 40;
+        let base = 2 * 7 + 4 * 3;
+        assert_eq!(
+            run.policy().score(&run.metrics, &run.effects),
+            base + base * 5 / 100
+        );
+        run.abandoned = true;
+        engine.finish_run(&run).unwrap();
+        let row = &engine.leaderboards.rows(GameMode::CUSTOM)[0];
+        assert_eq!(row.preset.as_deref(), Some("Loose"));
+        assert_eq!(row.loadout_summary, "soft-wrap");
+        assert!(engine.leaderboards.rows(GameMode::INVINCIBLE).is_empty());
+
+        engine.profile.save_custom_preset(rules.clone());
+        rules.wraps = false;
+        engine.profile.save_custom_preset(rules.clone());
+        assert_eq!(engine.profile.custom_presets, vec![rules]);
+    }
+
+    fn fill_board(run: &mut GameRun) {
+        run.board = Board {
+            width: 3,
+            height: 1,
+        };
+        run.snake = vec![
+            Point { x: 2, y: 0 },
+            Point { x: 1, y: 0 },
+            Point { x: 0, y: 0 },
+        ];
+    }
+
+    #[test]
+    fn full_board_wins_with_completion_bonus_and_perfect_flag() {
+        let mut engine = GameEngine::new(Profile::default());
+        let mut run = engine.start_run(GameMode::PRACTICE, None).unwrap();
+        assert!(!engine.check_board_full(&mut run));
+        assert_eq!(run.end_state(), RunEnd::Continue);
+
+        fill_board(&mut run);
+        assert!(engine.check_board_full(&mut run));
+        assert_eq!(run.end_state(), RunEnd::Won);
+        assert_eq!(
+            run.drain_events(),
+            vec![RunEvent::BoardFilled { length: 3 }]
+        );
+        assert!(run.is_perfect());
+
+        engine.finish_run(&run).unwrap();
+        let row = &engine.leaderboards.rows(GameMode::PRACTICE)[0];
+        assert!(row.perfect);
+        assert_eq!(row.score, PracticePolicy.completion_bonus(run.board));
+        assert_eq!(row.score, 30);
+    }
+
+    #[test]
+    fn invincible_collision_on_full_board_wins_instead_of_failing() {
+        let mut engine = GameEngine::new(Profile::default());
+        let mut run = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
+        run.metrics.repositions = 1;
+        fill_board(&mut run);
+
+        engine
+            .handle_collision(&mut run, Point { x: 0, y: 0 })
+            .unwrap();
+        assert!(run.ended && run.won);
+        assert!(!run.is_perfect());
+        engine.finish_run(&run).unwrap();
+        assert!(!engine.leaderboards.rows(GameMode::INVINCIBLE)[0].perfect);
+    }
+
     #[test]
     fn challenge_rows_keep_mode_and_loadout_metadata() {
         let mut engine = GameEngine::new(unlocked_profile());
-        let mut run = engine.start_run(GameMode::Challenge, None).unwrap();
+        let mut run = engine.start_run(GameMode::CHALLENGE, None).unwrap();
         run.metrics.survival_ticks = 7;
         run.metrics.food_eaten = 2;
         engine.finish_run(&run).unwrap();
 
-        let row = &engine.leaderboards.rows(GameMode::Challenge)[0];
-        assert_eq!(row.mode, GameMode::Challenge);
+        let row = &engine.leaderboards.rows(GameMode::CHALLENGE)[0];
+        assert_eq!(row.mode, GameMode::CHALLENGE);
         assert_eq!(row.loadout_summary, "none");
     }
 
@@ -889,14 +3119,14 @@ mod tests {
     fn e2e_mode_rule_coverage() {
         let mut engine = GameEngine::new(unlocked_profile());
 
-        let mut practice = engine.start_run(GameMode::Practice, None).unwrap();
+        let mut practice = engine.start_run(GameMode::PRACTICE, None).unwrap();
         practice.tick();
         engine
             .handle_collision(&mut practice, Point { x: 0, y: 0 })
             .unwrap();
         assert!(practice.ended);
 
-        let mut invincible = engine.start_run(GameMode::Invincible, None).unwrap();
+        let mut invincible = engine.start_run(GameMode::INVINCIBLE, None).unwrap();
         invincible.add_food(50);
         engine
             .handle_collision(&mut invincible, Point { x: 5, y: 5 })
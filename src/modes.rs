@@ -0,0 +1,256 @@
+//! Game modes and the registry menus and leaderboards are built from. A mode
+//! is defined once, with its policy and loadout requirement, and registered
+//! on the engine; crates embedding the game can register their own alongside
+//! the built-in ones.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{
+    ArenaPolicy, CampaignPolicy, ChallengePolicy, CustomPolicy, CustomRules, ExperimentalPolicy,
+    InvinciblePolicy, LeaderboardEntry, ModePolicy, PracticePolicy, PuzzlePolicy, TimeAttackPolicy,
+};
+
+pub struct ModeDefinition {
+    /// Stable key for leaderboards, settings and asset names.
+    pub id: &'static str,
+    pub label: &'static str,
+    /// Rules, scoring and leaderboard ordering, the last through
+    /// [`ModePolicy::ranking`].
+    pub policy: fn() -> Box<dyn ModePolicy>,
+    /// Runs need a three-tool loadout picked before they start.
+    pub requires_loadout: bool,
+}
+
+/// A handle to a mode's [`ModeDefinition`]. Modes compare, hash and order by
+/// id.
+#[derive(Clone, Copy)]
+pub struct GameMode(&'static ModeDefinition);
+
+impl GameMode {
+    pub const PRACTICE: GameMode = GameMode(&ModeDefinition {
+        id: "practice",
+        label: "Practice",
+        policy: || Box::new(PracticePolicy),
+        requires_loadout: false,
+    });
+    pub const CHALLENGE: GameMode = GameMode(&ModeDefinition {
+        id: "challenge",
+        label: "Challenge",
+        policy: || Box::new(ChallengePolicy),
+        requires_loadout: false,
+    });
+    pub const EXPERIMENTAL: GameMode = GameMode(&ModeDefinition {
+        id: "experimental",
+        label: "Experimental",
+        policy: || Box::new(ExperimentalPolicy),
+        requires_loadout: true,
+    });
+    pub const INVINCIBLE: GameMode = GameMode(&ModeDefinition {
+        id: "invincible",
+        label: "Invincible",
+        policy: || Box::new(InvinciblePolicy),
+        requires_loadout: false,
+    });
+    pub const TIME_ATTACK: GameMode = GameMode(&ModeDefinition {
+        id: "time-attack",
+        label: "Time Attack",
+        policy: || Box::new(TimeAttackPolicy),
+        requires_loadout: false,
+    });
+    pub const PUZZLE: GameMode = GameMode(&ModeDefinition {
+        id: "puzzle",
+        label: "Puzzle",
+        policy: || Box::new(PuzzlePolicy),
+        requires_loadout: false,
+    });
+    pub const CAMPAIGN: GameMode = GameMode(&ModeDefinition {
+        id: "campaign",
+        label: "Campaign",
+        policy: || Box::new(CampaignPolicy),
+        requires_loadout: false,
+    });
+    pub const ARENA: GameMode = GameMode(&ModeDefinition {
+        id: "arena",
+        label: "Arena",
+        policy: || Box::new(ArenaPolicy),
+        requires_loadout: false,
+    });
+    /// Runs carry their own rules; this policy only holds the defaults.
+    pub const CUSTOM: GameMode = GameMode(&ModeDefinition {
+        id: "custom",
+        label: "Custom",
+        policy: || {
+            Box::new(CustomPolicy {
+                rules: CustomRules::default(),
+            })
+        },
+        requires_loadout: false,
+    });
+
+    pub const fn new(definition: &'static ModeDefinition) -> Self {
+        Self(definition)
+    }
+
+    pub fn id(self) -> &'static str {
+        self.0.id
+    }
+
+    pub fn label(self) -> &'static str {
+        self.0.label
+    }
+
+    pub fn policy(self) -> Box<dyn ModePolicy> {
+        (self.0.policy)()
+    }
+
+    /// Leaderboard order from the mode's ranking; `Less` ranks first.
+    pub fn compare(self, a: &LeaderboardEntry, b: &LeaderboardEntry) -> Ordering {
+        self.policy().ranking().compare(a, b)
+    }
+
+    pub fn requires_loadout(self) -> bool {
+        self.0.requires_loadout
+    }
+}
+
+impl PartialEq for GameMode {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for GameMode {}
+
+impl Hash for GameMode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
+impl PartialOrd for GameMode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GameMode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id().cmp(other.id())
+    }
+}
+
+impl fmt::Debug for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("GameMode").field(&self.id()).finish()
+    }
+}
+
+/// Registered modes in menu order.
+#[derive(Debug, Clone)]
+pub struct ModeRegistry {
+    modes: Vec<GameMode>,
+}
+
+impl ModeRegistry {
+    pub fn builtin() -> Self {
+        Self {
+            modes: vec![
+                GameMode::PRACTICE,
+                GameMode::CHALLENGE,
+                GameMode::EXPERIMENTAL,
+                GameMode::INVINCIBLE,
+                GameMode::TIME_ATTACK,
+                GameMode::PUZZLE,
+                GameMode::CAMPAIGN,
+                GameMode::ARENA,
+                GameMode::CUSTOM,
+            ],
+        }
+    }
+
+    /// Adds `mode` at the end of the menus. Ids must be unique.
+    pub fn register(&mut self, mode: GameMode) -> Result<(), String> {
+        if self.modes.contains(&mode) {
+            return Err(format!("mode already registered: {}", mode.id()));
+        }
+        self.modes.push(mode);
+        Ok(())
+    }
+
+    pub fn list(&self) -> &[GameMode] {
+        &self.modes
+    }
+
+    pub fn get(&self, id: &str) -> Option<GameMode> {
+        self.modes.iter().copied().find(|mode| mode.id() == id)
+    }
+
+    /// Menu position of `mode`, or 0 if it isn't registered.
+    pub fn index_of(&self, mode: GameMode) -> usize {
+        self.modes
+            .iter()
+            .position(|candidate| *candidate == mode)
+            .unwrap_or(0)
+    }
+}
+
+impl Default for ModeRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActiveEffects, CollisionOutcome, GameEngine, Profile, RunEnd, RunMetrics};
+
+    struct ZenPolicy;
+
+    impl ModePolicy for ZenPolicy {
+        fn mode(&self) -> GameMode {
+            ZEN
+        }
+
+        fn collision_outcome(&self) -> CollisionOutcome {
+            CollisionOutcome::Reposition
+        }
+
+        fn score(&self, metrics: &RunMetrics, _effects: &ActiveEffects) -> u64 {
+            metrics.survival_ticks
+        }
+
+        fn run_end_state(&self, _replay_on_death: bool) -> RunEnd {
+            RunEnd::Continue
+        }
+    }
+
+    const ZEN: GameMode = GameMode::new(&ModeDefinition {
+        id: "zen",
+        label: "Zen",
+        policy: || Box::new(ZenPolicy),
+        requires_loadout: false,
+    });
+
+    #[test]
+    fn extra_modes_register_once_after_the_builtins() {
+        let mut modes = ModeRegistry::builtin();
+        modes.register(ZEN).unwrap();
+        assert!(modes.register(ZEN).is_err());
+        assert!(modes.register(GameMode::ARENA).is_err());
+        assert_eq!(modes.get("zen"), Some(ZEN));
+        assert_eq!(modes.index_of(ZEN), modes.list().len() - 1);
+        assert_eq!(ZEN.policy().mode(), ZEN);
+        assert_eq!(format!("{ZEN:?}"), "GameMode(\"zen\")");
+
+        let mut engine = GameEngine::with_modes(Profile::default(), modes);
+        assert!(engine.build_menu_items().contains(&"Start Zen".to_string()));
+        let mut run = engine.start_run(ZEN, None).unwrap();
+        run.metrics.survival_ticks = 42;
+        run.abandoned = true;
+        engine.finish_run(&run).unwrap();
+        assert_eq!(engine.leaderboards.rows(ZEN)[0].score, 42);
+    }
+}
@@ -0,0 +1,228 @@
+//! Hand-authored puzzle levels: a fixed layout, fixed food, a move budget and
+//! a goal. Runs started from a puzzle carry an [`ActivePuzzle`] that the
+//! engine checks after every move.
+
+use crate::{Board, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleGoal {
+    EatAll,
+    ReachExit(Point),
+    ReachLength(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Puzzle {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub board: Board,
+    /// Head first; the first two segments give the starting heading.
+    pub snake: &'static [Point],
+    pub walls: &'static [Point],
+    pub foods: &'static [Point],
+    pub goal: PuzzleGoal,
+    pub move_budget: u32,
+    /// Moves in the shortest known solution; meeting it earns three stars.
+    pub par_moves: u32,
+}
+
+const fn p(x: i32, y: i32) -> Point {
+    Point { x, y }
+}
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle {
+        id: "first-bite",
+        name: "First Bite",
+        board: Board {
+            width: 6,
+            height: 4,
+        },
+        snake: &[p(1, 1), p(0, 1)],
+        walls: &[],
+        foods: &[p(4, 1), p(2, 3)],
+        goal: PuzzleGoal::EatAll,
+        move_budget: 12,
+        par_moves: 7,
+    },
+    Puzzle {
+        id: "around-the-wall",
+        name: "Around the Wall",
+        board: Board {
+            width: 7,
+            height: 5,
+        },
+        snake: &[p(1, 1), p(0, 1)],
+        walls: &[p(3, 0), p(3, 1), p(3, 2), p(3, 3)],
+        foods: &[],
+        goal: PuzzleGoal::ReachExit(p(5, 1)),
+        move_budget: 14,
+        par_moves: 10,
+    },
+    Puzzle {
+        id: "grow-up",
+        name: "Grow Up",
+        board: Board {
+            width: 6,
+            height: 6,
+        },
+        snake: &[p(1, 0), p(0, 0)],
+        walls: &[p(3, 3), p(2, 4)],
+        foods: &[p(2, 0), p(4, 0), p(4, 2), p(2, 2)],
+        goal: PuzzleGoal::ReachLength(5),
+        move_budget: 9,
+        par_moves: 5,
+    },
+    Puzzle {
+        id: "corridor",
+        name: "Corridor",
+        board: Board {
+            width: 8,
+            height: 3,
+        },
+        snake: &[p(1, 0), p(0, 0)],
+        walls: &[p(1, 1), p(2, 1), p(3, 1), p(4, 1), p(5, 1), p(6, 1)],
+        foods: &[p(7, 0), p(0, 2)],
+        goal: PuzzleGoal::EatAll,
+        move_budget: 20,
+        par_moves: 15,
+    },
+    Puzzle {
+        id: "the-pocket",
+        name: "The Pocket",
+        board: Board {
+            width: 6,
+            height: 6,
+        },
+        snake: &[p(1, 5), p(0, 5)],
+        walls: &[p(2, 1), p(2, 2), p(2, 3), p(3, 3), p(4, 3)],
+        foods: &[p(4, 5)],
+        goal: PuzzleGoal::ReachExit(p(3, 2)),
+        move_budget: 12,
+        par_moves: 9,
+    },
+];
+
+pub fn puzzle(id: &str) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|puzzle| puzzle.id == id)
+}
+
+impl Puzzle {
+    /// Three stars at par, two within half the spare budget, otherwise one.
+    pub fn stars(&self, moves_used: u32) -> u8 {
+        let spare = self.move_budget.saturating_sub(self.par_moves);
+        if moves_used <= self.par_moves {
+            3
+        } else if moves_used <= self.par_moves + spare / 2 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// Progress through the puzzle a run was started from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActivePuzzle {
+    pub puzzle: &'static Puzzle,
+    pub moves_used: u32,
+}
+
+impl ActivePuzzle {
+    pub fn moves_remaining(&self) -> u32 {
+        self.puzzle.move_budget.saturating_sub(self.moves_used)
+    }
+
+    pub fn goal_met(&self, snake: &[Point], foods_left: usize) -> bool {
+        match self.puzzle.goal {
+            PuzzleGoal::EatAll => foods_left == 0,
+            PuzzleGoal::ReachExit(exit) => snake.first() == Some(&exit),
+            PuzzleGoal::ReachLength(length) => snake.len() >= length,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashSet, VecDeque};
+
+    use super::*;
+
+    const STEPS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+    /// Breadth-first search over snake bodies and remaining food, using the
+    /// same move, growth and collision rules as a running game. Returns the
+    /// fewest moves that reach the goal within the budget.
+    fn solve(puzzle: &'static Puzzle) -> Option<u32> {
+        let start = (puzzle.snake.to_vec(), puzzle.foods.to_vec());
+        let mut seen = HashSet::new();
+        seen.insert(start.clone());
+        let mut queue = VecDeque::from([(start, 0)]);
+        while let Some(((snake, foods), moves)) = queue.pop_front() {
+            if moves >= puzzle.move_budget {
+                continue;
+            }
+            for (dx, dy) in STEPS {
+                let head = Point {
+                    x: snake[0].x + dx,
+                    y: snake[0].y + dy,
+                };
+                if head == snake[1] || !head.within(puzzle.board) || puzzle.walls.contains(&head) {
+                    continue;
+                }
+                let eats = foods.contains(&head);
+                let body_limit = if eats { snake.len() } else { snake.len() - 1 };
+                if snake[..body_limit].contains(&head) {
+                    continue;
+                }
+                let mut next_snake = snake.clone();
+                next_snake.insert(0, head);
+                if !eats {
+                    next_snake.pop();
+                }
+                let next_foods: Vec<Point> =
+                    foods.iter().copied().filter(|food| *food != head).collect();
+                let active = ActivePuzzle {
+                    puzzle,
+                    moves_used: moves + 1,
+                };
+                if active.goal_met(&next_snake, next_foods.len()) {
+                    return Some(moves + 1);
+                }
+                let state = (next_snake, next_foods);
+                if seen.insert(state.clone()) {
+                    queue.push_back((state, moves + 1));
+                }
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn every_shipped_puzzle_is_solvable_at_par() {
+        for puzzle in PUZZLES {
+            assert_eq!(solve(puzzle), Some(puzzle.par_moves), "{}", puzzle.id);
+        }
+    }
+
+    #[test]
+    fn layouts_stay_on_the_board_without_overlaps() {
+        let mut ids = HashSet::new();
+        for puzzle in PUZZLES {
+            assert!(ids.insert(puzzle.id), "duplicate id {}", puzzle.id);
+            assert!(puzzle.snake.len() >= 2 && puzzle.par_moves <= puzzle.move_budget);
+            let cells: Vec<Point> = [puzzle.snake, puzzle.walls, puzzle.foods].concat();
+            let unique: HashSet<Point> = cells.iter().copied().collect();
+            assert_eq!(unique.len(), cells.len(), "{}", puzzle.id);
+            assert!(cells.iter().all(|cell| cell.within(puzzle.board)));
+        }
+    }
+
+    #[test]
+    fn stars_drop_as_moves_pass_par() {
+        let puzzle = puzzle("first-bite").unwrap();
+        assert_eq!(puzzle.stars(7), 3);
+        assert_eq!(puzzle.stars(9), 2);
+        assert_eq!(puzzle.stars(12), 1);
+    }
+}